@@ -0,0 +1,357 @@
+use std::{collections::HashMap, error::Error, fmt::Write, fs, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// The site asks that private leaderboards are requested at most once every 15 minutes
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// ID of the private leaderboard (the number at the end of its URL)
+    id: u64,
+
+    /// Show star timestamps for a single day instead of the overall rankings
+    #[arg(short, long)]
+    day: Option<u8>,
+
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Deserialize)]
+struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    stars: u32,
+    local_score: u32,
+    completion_day_level: HashMap<u8, HashMap<u8, Star>>,
+}
+
+#[derive(Deserialize)]
+struct Star {
+    get_star_ts: i64,
+    star_index: u64,
+}
+
+impl Member {
+    fn star(&self, day: u8, part: u8) -> Option<&Star> {
+        self.completion_day_level
+            .get(&day)
+            .and_then(|parts| parts.get(&part))
+    }
+
+    fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Serialize)]
+struct Standing {
+    id: u64,
+    name: String,
+    stars: u32,
+    /// Score recomputed from the star timestamps
+    local_score: u32,
+    /// Score as reported by the site
+    site_score: u32,
+    days: Vec<DayResult>,
+}
+
+#[derive(Serialize)]
+struct DayResult {
+    day: u8,
+    part1_ts: Option<i64>,
+    part2_ts: Option<i64>,
+    /// Seconds between the first and second star
+    delta: Option<i64>,
+    points: u32,
+}
+
+pub async fn run(
+    client: &reqwest::Client,
+    year: u16,
+    cache_dir: &Path,
+    args: Args,
+) -> Result<(), Box<dyn Error>> {
+    let leaderboard = fetch(client, year, args.id, cache_dir).await?;
+    let mut standings = standings(&leaderboard);
+    if let Some(day) = args.day {
+        for standing in standings.iter_mut() {
+            standing.days.retain(|d| d.day == day);
+        }
+    }
+    let output = match (args.format, args.day) {
+        (Format::Table, None) => render_rankings(&standings),
+        (Format::Table, Some(day)) => render_day(&standings, year, day),
+        (Format::Json, _) => serde_json::to_string_pretty(&standings)? + "\n",
+        (Format::Csv, _) => render_csv(&standings),
+    };
+    print!("{output}");
+    Ok(())
+}
+
+async fn fetch(
+    client: &reqwest::Client,
+    year: u16,
+    id: u64,
+    cache_dir: &Path,
+) -> Result<Leaderboard, Box<dyn Error>> {
+    let path = cache_dir.join(format!("leaderboard/{year}/{id}.json"));
+    if let Ok(meta) = fs::metadata(&path) {
+        if meta.modified()?.elapsed().is_ok_and(|age| age < CACHE_TTL) {
+            return Ok(serde_json::from_str(&fs::read_to_string(path)?)?);
+        }
+    }
+
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+    let body = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let leaderboard = serde_json::from_str(&body)
+        .map_err(|e| format!("unexpected leaderboard response ({e}), is the token valid?"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, &body)?;
+    Ok(leaderboard)
+}
+
+/// Recompute each member's local score: the first member to get a star receives one point per
+/// member on the leaderboard, the second one point less, and so on.
+fn standings(leaderboard: &Leaderboard) -> Vec<Standing> {
+    let n = leaderboard.members.len() as u32;
+    let mut points: HashMap<(u64, u8), u32> = HashMap::new();
+    for day in 1..=25 {
+        for part in 1..=2 {
+            let mut finishers = leaderboard
+                .members
+                .values()
+                .filter_map(|m| {
+                    m.star(day, part)
+                        .map(|s| (s.get_star_ts, s.star_index, m.id))
+                })
+                .collect::<Vec<_>>();
+            finishers.sort();
+            for (rank, &(_, _, id)) in finishers.iter().enumerate() {
+                *points.entry((id, day)).or_default() += n - rank as u32;
+            }
+        }
+    }
+
+    let mut standings = leaderboard
+        .members
+        .values()
+        .map(|m| {
+            let days = (1..=25)
+                .filter(|day| m.completion_day_level.contains_key(day))
+                .map(|day| {
+                    let part1_ts = m.star(day, 1).map(|s| s.get_star_ts);
+                    let part2_ts = m.star(day, 2).map(|s| s.get_star_ts);
+                    DayResult {
+                        day,
+                        part1_ts,
+                        part2_ts,
+                        delta: part1_ts.zip(part2_ts).map(|(a, b)| b - a),
+                        points: points.get(&(m.id, day)).copied().unwrap_or(0),
+                    }
+                })
+                .collect::<Vec<_>>();
+            Standing {
+                id: m.id,
+                name: m.display_name(),
+                stars: m.stars,
+                local_score: days.iter().map(|d| d.points).sum(),
+                site_score: m.local_score,
+                days,
+            }
+        })
+        .collect::<Vec<_>>();
+    standings.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.id.cmp(&b.id))
+    });
+    standings
+}
+
+fn render_rankings(standings: &[Standing]) -> String {
+    let mut out = crate::calendar_header(18);
+    let _ = writeln!(
+        out,
+        "{:>4} {:>5} {:>5}  {:25}  Name",
+        "#", "Score", "Stars", ""
+    );
+    for (rank, s) in standings.iter().enumerate() {
+        let calendar = (1..=25)
+            .map(|day| match s.days.iter().find(|d| d.day == day) {
+                Some(DayResult {
+                    part2_ts: Some(_), ..
+                }) => '*',
+                Some(_) => '+',
+                None => '.',
+            })
+            .collect::<String>();
+        let _ = writeln!(
+            out,
+            "{:>3}) {:>5} {:>5}  {calendar}  {}",
+            rank + 1,
+            s.local_score,
+            s.stars,
+            s.name
+        );
+    }
+
+    let mismatched = standings
+        .iter()
+        .filter(|s| s.local_score != s.site_score)
+        .map(|s| format!("{} ({} on site)", s.name, s.site_score))
+        .collect::<Vec<_>>();
+    if !mismatched.is_empty() {
+        let _ = writeln!(
+            out,
+            "\nRecomputed scores differ for: {}",
+            mismatched.join(", ")
+        );
+    }
+    out
+}
+
+fn render_day(standings: &[Standing], year: u16, day: u8) -> String {
+    let unlock = unlock_ts(year, day);
+    let mut rows = standings
+        .iter()
+        .filter_map(|s| s.days.first().map(|d| (s, d)))
+        .collect::<Vec<_>>();
+    rows.sort_by_key(|(_, d)| (d.part2_ts.unwrap_or(i64::MAX), d.part1_ts));
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:>4} {:>9} {:>9} {:>9} {:>6}  Name",
+        "#", "Part 1", "Part 2", "Delta", "Points"
+    );
+    for (rank, (s, d)) in rows.into_iter().enumerate() {
+        let elapsed = |ts: Option<i64>| ts.map_or("-".into(), |ts| format_duration(ts - unlock));
+        let _ = writeln!(
+            out,
+            "{:>3}) {:>9} {:>9} {:>9} {:>6}  {}",
+            rank + 1,
+            elapsed(d.part1_ts),
+            elapsed(d.part2_ts),
+            d.delta.map_or("-".into(), format_duration),
+            d.points,
+            s.name
+        );
+    }
+    out
+}
+
+fn render_csv(standings: &[Standing]) -> String {
+    let mut out =
+        String::from("rank,id,name,local_score,stars,day,part1_ts,part2_ts,delta,points\n");
+    let opt = |v: Option<i64>| v.map(|v| v.to_string()).unwrap_or_default();
+    for (rank, s) in standings.iter().enumerate() {
+        for d in &s.days {
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{}",
+                rank + 1,
+                s.id,
+                csv_field(&s.name),
+                s.local_score,
+                s.stars,
+                d.day,
+                opt(d.part1_ts),
+                opt(d.part2_ts),
+                opt(d.delta),
+                d.points
+            );
+        }
+    }
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn format_duration(secs: i64) -> String {
+    if secs >= 24 * 60 * 60 {
+        return ">24h".into();
+    }
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Puzzles unlock at midnight EST (UTC-5)
+fn unlock_ts(year: u16, day: u8) -> i64 {
+    // Days since the unix epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let (y, m, d) = (year as i64, 12, day as i64);
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (m - 3) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+    days * 24 * 60 * 60 + 5 * 60 * 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"{
+        "event": "2024",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "alice", "stars": 2, "local_score": 6, "global_score": 0, "last_star_ts": 1733030000,
+                "completion_day_level": {"1": {"1": {"get_star_ts": 1733029500, "star_index": 10}, "2": {"get_star_ts": 1733030000, "star_index": 20}}}},
+            "2": {"id": 2, "name": null, "stars": 1, "local_score": 3, "global_score": 0, "last_star_ts": 1733029400,
+                "completion_day_level": {"1": {"1": {"get_star_ts": 1733029400, "star_index": 5}}}},
+            "3": {"id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {}}
+        }
+    }"#;
+
+    #[test]
+    fn recompute_local_score() -> Result<(), Box<dyn Error>> {
+        let leaderboard = serde_json::from_str(EXAMPLE)?;
+        let standings = standings(&leaderboard);
+        let scores = standings
+            .iter()
+            .map(|s| (s.name.as_str(), s.local_score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            scores,
+            [("alice", 5), ("(anonymous user #2)", 3), ("carol", 0)]
+        );
+        assert_eq!(standings[0].days[0].delta, Some(500));
+        Ok(())
+    }
+
+    #[test]
+    fn unlock_time() {
+        assert_eq!(unlock_ts(2024, 1), 1733029200);
+    }
+}
@@ -1,19 +1,50 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
-use clap::Parser;
+use clap::{CommandFactory, Parser, Subcommand};
 
-/// Fetch the puzzle input from adventofcode.com for the given day
+mod leaderboard;
+mod solve;
+mod status;
+
+/// Command line client for adventofcode.com
 #[derive(Parser, Debug)]
+#[command(subcommand_precedence_over_arg = true)]
 struct Args {
-    /// Day to fetch puzzle input for (1-25)
-    day: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Day to fetch puzzle input for (1-25), the same as `aoc input <day>`
+    day: Option<u8>,
 
-    #[arg(short, long, default_value = "2024")]
+    #[arg(short, long, default_value = "2024", global = true)]
     year: u16,
 
     /// The value of your "session" cookie on adventofcode.com
-    #[arg(short, long, env = "AOC_TOKEN")]
-    token: String,
+    #[arg(short, long, env = "AOC_TOKEN", global = true)]
+    token: Option<String>,
+
+    /// Directory used to cache responses from adventofcode.com
+    #[arg(long, env = "AOC_CACHE_DIR", default_value = ".cache", global = true)]
+    cache_dir: PathBuf,
+
+    /// Root of the workspace holding the `puzzle-{year}` crates and `input/{year}` directories
+    #[arg(long, env = "AOC_ROOT", default_value = ".", global = true)]
+    root: PathBuf,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Fetch the puzzle input for the given day
+    Input {
+        /// Day to fetch puzzle input for (1-25)
+        day: u8,
+    },
+    /// Show the rankings of a private leaderboard
+    Leaderboard(leaderboard::Args),
+    /// Run the solver for a day and submit its answer
+    Solve(solve::Args),
+    /// Compare stars on the event calendar with the local solvers and inputs
+    Status(status::Args),
 }
 
 #[tokio::main]
@@ -21,7 +52,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
     let cookie_url = "https://adventofcode.com".parse::<reqwest::Url>()?;
-    let token = args.token;
+    let Some(token) = args.token else {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::MissingRequiredArgument,
+                "expected --token <TOKEN> or AOC_TOKEN in the environment",
+            )
+            .exit()
+    };
     let cookie = format!("session={token}");
     let cookie_jar = reqwest::cookie::Jar::default();
     cookie_jar.add_cookie_str(&cookie, &cookie_url);
@@ -30,9 +68,83 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build()?;
 
     let year = args.year;
-    let day = args.day;
-    let input_url = format!("https://adventofcode.com/{year}/day/{day}/input");
-    let resp = client.get(input_url).send().await?.text().await?;
-    print!("{resp}");
+    let command = match (args.command, args.day) {
+        (Some(command), _) => command,
+        (None, Some(day)) => Command::Input { day },
+        (None, None) => Args::command()
+            .error(
+                clap::error::ErrorKind::MissingRequiredArgument,
+                "expected a day or a subcommand",
+            )
+            .exit(),
+    };
+    match command {
+        Command::Input { day } => {
+            print!("{}", fetch_input(&client, year, day).await?);
+        }
+        Command::Leaderboard(lb_args) => {
+            leaderboard::run(&client, year, &args.cache_dir, lb_args).await?;
+        }
+        Command::Solve(solve_args) => {
+            solve::run(&client, year, &args.root, &args.cache_dir, solve_args).await?;
+        }
+        Command::Status(status_args) => {
+            status::run(&client, year, &args.root, status_args).await?;
+        }
+    }
     Ok(())
 }
+
+async fn fetch_input(
+    client: &reqwest::Client,
+    year: u16,
+    day: u8,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let input_url = format!("https://adventofcode.com/{year}/day/{day}/input");
+    let resp = client
+        .get(input_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    Ok(resp)
+}
+
+/// Two header lines numbering the days of the calendar, starting at the given column
+fn calendar_header(indent: usize) -> String {
+    let tens = (1..=25)
+        .map(|d| if d < 10 { ' ' } else { (b'0' + d / 10) as char })
+        .collect::<String>();
+    let ones = (1..=25)
+        .map(|d| (b'0' + d % 10) as char)
+        .collect::<String>();
+    format!("{:indent$}{tens}\n{:indent$}{ones}\n", "", "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_with_day_or_subcommand() -> Result<(), clap::Error> {
+        let args = Args::try_parse_from(["aoc", "-t", "X", "3"])?;
+        assert_eq!((args.token.as_deref(), args.day), (Some("X"), Some(3)));
+        assert!(args.command.is_none());
+
+        for argv in [
+            ["aoc", "-t", "X", "leaderboard", "1"],
+            ["aoc", "leaderboard", "1", "-t", "X"],
+        ] {
+            let args = Args::try_parse_from(argv)?;
+            assert_eq!(args.token.as_deref(), Some("X"));
+            assert!(matches!(args.command, Some(Command::Leaderboard(_))));
+        }
+
+        let args = Args::try_parse_from(["aoc", "input", "3", "-t", "X"])?;
+        assert!(matches!(args.command, Some(Command::Input { day: 3 })));
+        let args = Args::try_parse_from(["aoc", "-t", "X", "status"])?;
+        assert!(matches!(args.command, Some(Command::Status(_))));
+        Ok(())
+    }
+}
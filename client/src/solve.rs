@@ -0,0 +1,297 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Stdio,
+    str::FromStr,
+};
+
+use tokio::process::Command;
+
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Day to solve (1-25)
+    day: u8,
+
+    /// Part to submit, defaults to the first part without a correct answer
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Submit without asking for confirmation
+    #[arg(long)]
+    yes: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl FromStr for Verdict {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "wrong" => Ok(Self::Wrong),
+            "too_high" => Ok(Self::TooHigh),
+            "too_low" => Ok(Self::TooLow),
+            _ => Err("unknown verdict"),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Correct => "correct",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too_high",
+            Self::TooLow => "too_low",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Answers previously submitted for one day, stored as `part\tanswer\tverdict` lines
+struct History {
+    path: PathBuf,
+    entries: Vec<(u8, String, Verdict)>,
+}
+
+impl History {
+    fn load(path: PathBuf) -> Result<Self, Box<dyn Error>> {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let part = fields.next()?.parse().ok()?;
+                let answer = fields.next()?.to_string();
+                let verdict = fields.next()?.parse().ok()?;
+                Some((part, answer, verdict))
+            })
+            .collect();
+        Ok(Self { path, entries })
+    }
+
+    fn record(&mut self, part: u8, answer: &str, verdict: Verdict) -> Result<(), Box<dyn Error>> {
+        self.entries.push((part, answer.to_string(), verdict));
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = self
+            .entries
+            .iter()
+            .map(|(part, answer, verdict)| format!("{part}\t{answer}\t{verdict}\n"))
+            .collect::<String>();
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    fn correct(&self, part: u8) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(p, _, v)| *p == part && *v == Verdict::Correct)
+            .map(|(_, answer, _)| answer.as_str())
+    }
+
+    /// Explain why an answer is known to be wrong without asking the site again
+    fn rejects(&self, part: u8, answer: &str) -> Option<String> {
+        let n = answer.parse::<i128>().ok();
+        self.entries
+            .iter()
+            .filter(|(p, _, _)| *p == part)
+            .find_map(|(_, prev, verdict)| {
+                let bound = prev.parse::<i128>().ok();
+                match (verdict, n, bound) {
+                    _ if prev == answer => Some(format!("{answer} was already rejected")),
+                    (Verdict::TooHigh, Some(n), Some(b)) if n >= b => {
+                        Some(format!("{answer} is not below {prev}, which was too high"))
+                    }
+                    (Verdict::TooLow, Some(n), Some(b)) if n <= b => {
+                        Some(format!("{answer} is not above {prev}, which was too low"))
+                    }
+                    _ => None,
+                }
+            })
+    }
+}
+
+pub async fn run(
+    client: &reqwest::Client,
+    year: u16,
+    root: &Path,
+    cache_dir: &Path,
+    args: Args,
+) -> Result<(), Box<dyn Error>> {
+    let day = args.day;
+    let input_path = root.join(format!("input/{year}/day{day:02}.txt"));
+    if !input_path.exists() {
+        let input = crate::fetch_input(client, year, day).await?;
+        if let Some(dir) = input_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&input_path, input)?;
+        eprintln!("Saved input to {}", input_path.display());
+    }
+
+    let mut history = History::load(cache_dir.join(format!("answers/{year}/day{day:02}.tsv")))?;
+    let part = args
+        .part
+        .unwrap_or(if history.correct(1).is_some() { 2 } else { 1 });
+    if let Some(answer) = history.correct(part) {
+        println!("Part {part} was already solved: {answer}");
+        return Ok(());
+    }
+
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--quiet", "--package"])
+        .arg(format!("puzzle-{year}"))
+        .arg("--bin")
+        .arg(format!("{year}-day{day:02}"))
+        .args(["--", "--format", "tsv"])
+        .current_dir(root)
+        .stderr(Stdio::inherit())
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(format!("{year}-day{day:02} exited with {}", output.status).into());
+    }
+    let stdout = String::from_utf8(output.stdout)?;
+    let answer = parse_answer(&stdout, part)
+        .ok_or_else(|| format!("no answer for part {part} in output:\n{stdout}"))?;
+
+    if let Some(reason) = history.rejects(part, &answer) {
+        return Err(format!("not submitting: {reason}").into());
+    }
+    if !args.yes
+        && !confirm(&format!(
+            "Submit {answer} for {year} day {day} part {part}?"
+        ))?
+    {
+        return Ok(());
+    }
+
+    let url = format!("https://adventofcode.com/{year}/day/{day}/answer");
+    let html = client
+        .post(url)
+        .form(&[("level", part.to_string()), ("answer", answer.clone())])
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let message = article_text(&html);
+    println!("{message}");
+    if let Some(verdict) = parse_verdict(&message) {
+        history.record(part, &answer, verdict)?;
+    }
+    Ok(())
+}
+
+/// Find the answer for the given part in the records printed by a solver run with
+/// `--format tsv`, which are `year day part answer read_us parse_us solve_us`
+fn parse_answer(stdout: &str, part: u8) -> Option<String> {
+    stdout.lines().skip(1).find_map(|line| {
+        let mut fields = line.split('\t').skip(2);
+        (fields.next()? == part.to_string())
+            .then(|| fields.next())
+            .flatten()
+            .map(str::to_string)
+    })
+}
+
+fn parse_verdict(message: &str) -> Option<Verdict> {
+    if message.contains("That's the right answer") {
+        Some(Verdict::Correct)
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Some(Verdict::TooHigh)
+        } else if message.contains("too low") {
+            Some(Verdict::TooLow)
+        } else {
+            Some(Verdict::Wrong)
+        }
+    } else {
+        // Rate limited or already solved, nothing was learned about the answer
+        None
+    }
+}
+
+/// The text of the first `<article>` in a response, with tags removed
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = true;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.trim().to_string()
+}
+
+fn confirm(prompt: &str) -> Result<bool, Box<dyn Error>> {
+    print!("{prompt} [y/N] ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(matches!(line.trim(), "y" | "Y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_records() {
+        let header = "year\tday\tpart\tanswer\tread_us\tparse_us\tsolve_us\n";
+        let part1 = format!("{header}2024\t4\t1\t18\t12\t\t120\n");
+        let both = format!("{part1}2024\t4\t2\t9\t12\t5\t80\n");
+        assert_eq!(parse_answer(&both, 1).as_deref(), Some("18"));
+        assert_eq!(parse_answer(&both, 2).as_deref(), Some("9"));
+        assert_eq!(parse_answer(&part1, 2), None);
+    }
+
+    #[test]
+    fn verdicts() {
+        let html = "<main><article><p>That's not the right answer; your answer is too high. \
+                    <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+        let message = article_text(html);
+        assert!(message.starts_with("That's not the right answer"));
+        assert!(message.ends_with("[Return to Day 1]"));
+        assert_eq!(parse_verdict(&message), Some(Verdict::TooHigh));
+    }
+
+    #[test]
+    fn history_rejects_known_bounds() {
+        let history = History {
+            path: PathBuf::new(),
+            entries: vec![
+                (1, "100".into(), Verdict::TooHigh),
+                (1, "10".into(), Verdict::TooLow),
+            ],
+        };
+        assert!(history.rejects(1, "100").is_some());
+        assert!(history.rejects(1, "150").is_some());
+        assert!(history.rejects(1, "5").is_some());
+        assert!(history.rejects(1, "50").is_none());
+        assert!(history.rejects(2, "150").is_none());
+    }
+}
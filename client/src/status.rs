@@ -0,0 +1,124 @@
+use std::{error::Error, fmt::Write, path::Path};
+
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Events to compare, defaults to the one selected by --year
+    years: Vec<u16>,
+}
+
+pub async fn run(
+    client: &reqwest::Client,
+    year: u16,
+    root: &Path,
+    args: Args,
+) -> Result<(), Box<dyn Error>> {
+    let years = if args.years.is_empty() {
+        vec![year]
+    } else {
+        args.years
+    };
+
+    let mut out = crate::calendar_header(6);
+    for year in years {
+        let url = format!("https://adventofcode.com/{year}");
+        let html = client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        if html.contains("/auth/login\"") {
+            return Err("not logged in, is the token valid?".into());
+        }
+        let stars = parse_calendar(&html);
+        let total = stars.iter().map(|&s| s as usize).sum::<usize>();
+        let calendar = stars
+            .iter()
+            .map(|s| ['.', '+', '*'][*s as usize])
+            .collect::<String>();
+        let _ = writeln!(out, "{year}  {calendar}  {total} stars");
+
+        let puzzle_dir = root.join(format!("puzzle-{year}"));
+        if !puzzle_dir.exists() {
+            continue;
+        }
+        let solvers = local_days(|day| puzzle_dir.join(format!("src/day{day:02}.rs")));
+        let inputs = local_days(|day| root.join(format!("input/{year}/day{day:02}.txt")));
+        let _ = writeln!(out, "      {}  local solvers", marks(&solvers));
+        let _ = writeln!(out, "      {}  cached inputs", marks(&inputs));
+
+        let missing = (0..25)
+            .filter(|&i| stars[i] == 2 && !solvers[i])
+            .map(|i| (i + 1).to_string())
+            .collect::<Vec<_>>();
+        let unsolved = (0..25)
+            .filter(|&i| stars[i] < 2 && solvers[i])
+            .map(|i| (i + 1).to_string())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let _ = writeln!(
+                out,
+                "      solved on the site, no local solver: {}",
+                missing.join(", ")
+            );
+        }
+        if !unsolved.is_empty() {
+            let _ = writeln!(
+                out,
+                "      local solver, not solved on the site: {}",
+                unsolved.join(", ")
+            );
+        }
+    }
+    print!("{out}");
+    Ok(())
+}
+
+/// Read the number of stars for each day from the aria labels of the event calendar,
+/// e.g. `aria-label="Day 3, two stars"`
+fn parse_calendar(html: &str) -> [u8; 25] {
+    let mut stars = [0; 25];
+    for label in html.split("aria-label=\"Day ").skip(1) {
+        let Some((label, _)) = label.split_once('"') else {
+            continue;
+        };
+        let (day, rest) = label.split_once(',').unwrap_or((label, ""));
+        let Ok(day @ 1..=25) = day.parse::<usize>() else {
+            continue;
+        };
+        stars[day - 1] = match rest.trim() {
+            "one star" => 1,
+            "two stars" => 2,
+            _ => 0,
+        };
+    }
+    stars
+}
+
+fn local_days(path: impl Fn(usize) -> std::path::PathBuf) -> [bool; 25] {
+    std::array::from_fn(|i| path(i + 1).exists())
+}
+
+fn marks(days: &[bool; 25]) -> String {
+    days.iter().map(|&d| if d { '#' } else { '.' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2024/day/1" class="calendar-day1 calendar-verycomplete">
+<a aria-label="Day 2, one star" href="/2024/day/2" class="calendar-day2 calendar-complete">
+<a aria-label="Day 3" href="/2024/day/3" class="calendar-day3">
+<span aria-hidden="true" class="calendar-day4">
+</pre>"#;
+
+    #[test]
+    fn calendar_stars() {
+        let stars = parse_calendar(EXAMPLE);
+        assert_eq!(stars[..4], [2, 1, 0, 0]);
+        assert_eq!(stars.iter().map(|&s| s as usize).sum::<usize>(), 3);
+    }
+}
@@ -0,0 +1,385 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display, Write},
+    fs::read_to_string,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// How a day reports its answers, selected with `--format json|tsv|plain`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// `p1: 11 (24µs)`
+    Plain,
+    /// A header line followed by one `year day part answer read_us parse_us solve_us` row per
+    /// part
+    Tsv,
+    /// One JSON object per line
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "tsv" => Ok(Self::Tsv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format {s:?}, expected json, tsv or plain")),
+        }
+    }
+}
+
+/// Anything a part can return as its answer
+pub trait Answer {
+    fn into_answer(self) -> Result<String, Box<dyn Error>>;
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn into_answer(self) -> Result<String, Box<dyn Error>> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+impl_answer!(i32, i64, u64, u128, usize, String);
+
+impl<T: Answer, E: Into<Box<dyn Error>>> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, Box<dyn Error>> {
+        self.map_err(Into::into)?.into_answer()
+    }
+}
+
+/// One answer with its timings
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// Time spent reading the input file
+    pub read_us: u128,
+    /// Time spent parsing the input, for parts solved with [`Runner::part_with`]. Other
+    /// parts parse as they solve, so their parsing counts towards `solve_us`.
+    pub parse_us: Option<u128>,
+    pub solve_us: u128,
+}
+
+impl Record {
+    pub fn format(&self, format: Format) -> String {
+        let Record {
+            year,
+            day,
+            part,
+            answer,
+            read_us,
+            parse_us,
+            solve_us,
+        } = self;
+        match format {
+            Format::Plain => match parse_us {
+                Some(parse_us) => format!("p{part}: {answer} ({solve_us}µs, parse {parse_us}µs)"),
+                None => format!("p{part}: {answer} ({solve_us}µs)"),
+            },
+            Format::Tsv => {
+                let parse_us = parse_us.map_or(String::new(), |us| us.to_string());
+                format!("{year}\t{day}\t{part}\t{answer}\t{read_us}\t{parse_us}\t{solve_us}")
+            }
+            Format::Json => {
+                let mut escaped = String::new();
+                for c in answer.chars() {
+                    match c {
+                        '"' | '\\' => {
+                            escaped.push('\\');
+                            escaped.push(c);
+                        }
+                        c if c.is_control() => {
+                            let _ = write!(escaped, "\\u{:04x}", c as u32);
+                        }
+                        c => escaped.push(c),
+                    }
+                }
+                let parse_us = parse_us.map_or("null".to_string(), |us| us.to_string());
+                format!(
+                    "{{\"year\":{year},\"day\":{day},\"part\":{part},\"answer\":\"{escaped}\",\"read_us\":{read_us},\"parse_us\":{parse_us},\"solve_us\":{solve_us}}}"
+                )
+            }
+        }
+    }
+}
+
+/// Loads the input for a day and prints the answer of each part in the format
+/// requested on the command line. Records are the only thing a day writes to stdout:
+/// explanations, renders and benchmarks go to stderr so `--format json|tsv` stays
+/// machine-readable.
+pub struct Runner {
+    year: u16,
+    day: u8,
+    format: Format,
+    args: Vec<String>,
+    input: String,
+    read_us: u128,
+}
+
+impl Runner {
+    /// Read `input/{year}/dayNN.txt` and the command line arguments
+    pub fn new(year: u16, day: u8) -> Result<Self, Box<dyn Error>> {
+        let args = env::args().skip(1).collect::<Vec<_>>();
+        let mut runner = Self {
+            year,
+            day,
+            format: Format::Plain,
+            args,
+            input: String::new(),
+            read_us: 0,
+        };
+        if let Some(format) = runner.value("--format") {
+            runner.format = format.parse()?;
+        }
+
+        let now = Instant::now();
+        runner.input = read_to_string(format!("input/{year}/day{day:02}.txt"))?;
+        runner.read_us = now.elapsed().as_micros();
+
+        if runner.format == Format::Tsv {
+            println!("year\tday\tpart\tanswer\tread_us\tparse_us\tsolve_us");
+        }
+        Ok(runner)
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Whether a flag such as `--explain` was passed on the command line
+    pub fn flag(&self, name: &str) -> bool {
+        self.args.iter().any(|arg| arg == name)
+    }
+
+    /// The value of an option passed as `--name value` or `--name=value`
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.args.iter().enumerate().find_map(|(i, arg)| {
+            if arg == name {
+                self.args.get(i + 1).map(String::as_str)
+            } else {
+                arg.strip_prefix(name)
+                    .and_then(|rest| rest.strip_prefix('='))
+            }
+        })
+    }
+
+    /// The value of an option passed as a `row,col` coordinate, such as `--start 9,5`
+    pub fn coord<T>(&self, name: &str) -> Result<Option<(T, T)>, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Error + 'static,
+    {
+        self.value(name).map(parse_coord).transpose()
+    }
+
+    /// Solve one part of the puzzle and print its record
+    pub fn part<A: Answer>(
+        &self,
+        part: u8,
+        solve: impl FnOnce(&str) -> A,
+    ) -> Result<Record, Box<dyn Error>> {
+        let now = Instant::now();
+        let answer = solve(&self.input).into_answer()?;
+        self.record(part, answer, None, now.elapsed().as_micros())
+    }
+
+    /// Solve one part of the puzzle in two steps, timing `parse` and `solve` separately
+    pub fn part_with<T, E: Into<Box<dyn Error>>, A: Answer>(
+        &self,
+        part: u8,
+        parse: impl FnOnce(&str) -> Result<T, E>,
+        solve: impl FnOnce(T) -> A,
+    ) -> Result<Record, Box<dyn Error>> {
+        let now = Instant::now();
+        let parsed = parse(&self.input).map_err(Into::into)?;
+        let parse_us = now.elapsed().as_micros();
+        let now = Instant::now();
+        let answer = solve(parsed).into_answer()?;
+        self.record(part, answer, Some(parse_us), now.elapsed().as_micros())
+    }
+
+    fn record(
+        &self,
+        part: u8,
+        answer: String,
+        parse_us: Option<u128>,
+        solve_us: u128,
+    ) -> Result<Record, Box<dyn Error>> {
+        let record = Record {
+            year: self.year,
+            day: self.day,
+            part,
+            answer,
+            read_us: self.read_us,
+            parse_us,
+            solve_us,
+        };
+        println!("{}", record.format(self.format));
+        Ok(record)
+    }
+}
+
+pub fn parse_coord<T>(s: &str) -> Result<(T, T), Box<dyn Error>>
+where
+    T: FromStr,
+    T::Err: Error + 'static,
+{
+    let (row, col) = s
+        .split_once(',')
+        .ok_or_else(|| format!("expected row,col but got {s:?}"))?;
+    Ok((row.trim().parse()?, col.trim().parse()?))
+}
+
+/// An intermediate value of a solver doesn't fit in the integer type it uses
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Overflow {
+    /// What the solver was computing, such as `"summing test values"`
+    pub context: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "integer overflow while {}", self.context)
+    }
+}
+
+impl Error for Overflow {}
+
+/// Turns the `None` of a `checked_*` operation into an [`Overflow`]
+pub trait OrOverflow<T> {
+    fn or_overflow(self, context: &'static str) -> Result<T, Overflow>;
+}
+
+impl<T> OrOverflow<T> for Option<T> {
+    fn or_overflow(self, context: &'static str) -> Result<T, Overflow> {
+        self.ok_or(Overflow { context })
+    }
+}
+
+/// A seeded xorshift generator for tests that compare implementations on random
+/// inputs, so that a failing case is the same on every run
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves an all-zero state
+        Self(seed.wrapping_mul(0x9e3779b97f4a7c15) | 1)
+    }
+
+    /// A number in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+/// Run `f` repeatedly and print its mean and fastest time, to compare alternative
+/// implementations of a part
+pub fn bench<T>(label: &str, iterations: u32, mut f: impl FnMut() -> T) {
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+    for _ in 0..iterations {
+        let now = Instant::now();
+        black_box(f());
+        let elapsed = now.elapsed();
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+    }
+    let mean = total / iterations.max(1);
+    eprintln!(
+        "{label}: mean {}µs, fastest {}µs over {iterations} runs",
+        mean.as_micros(),
+        fastest.as_micros()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Record {
+        Record {
+            year: 2024,
+            day: 4,
+            part: 2,
+            answer: "9".into(),
+            read_us: 12,
+            parse_us: Some(67),
+            solve_us: 345,
+        }
+    }
+
+    #[test]
+    fn formats() -> Result<(), Box<dyn Error>> {
+        let record = record();
+        assert_eq!(record.format("plain".parse()?), "p2: 9 (345µs, parse 67µs)");
+        assert_eq!(record.format("tsv".parse()?), "2024\t4\t2\t9\t12\t67\t345");
+        assert_eq!(
+            record.format("json".parse()?),
+            r#"{"year":2024,"day":4,"part":2,"answer":"9","read_us":12,"parse_us":67,"solve_us":345}"#
+        );
+
+        let record = Record {
+            parse_us: None,
+            ..record
+        };
+        assert_eq!(record.format(Format::Plain), "p2: 9 (345µs)");
+        assert_eq!(record.format(Format::Tsv), "2024\t4\t2\t9\t12\t\t345");
+        assert!(record.format(Format::Json).contains(r#""parse_us":null,"#));
+        assert!("xml".parse::<Format>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn json_escapes_answer() {
+        let record = Record {
+            answer: "a\"b\\c\n".into(),
+            ..record()
+        };
+        assert!(record
+            .format(Format::Json)
+            .contains(r#""answer":"a\"b\\c\u000a""#));
+    }
+
+    #[test]
+    fn coords() -> Result<(), Box<dyn Error>> {
+        assert_eq!(parse_coord::<i32>("9, -5")?, (9, -5));
+        assert_eq!(parse_coord::<usize>("0,3")?, (0, 3));
+        assert!(parse_coord::<usize>("-1,3").is_err());
+        assert!(parse_coord::<usize>("4").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rng_repeats_for_a_seed() {
+        let draw = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.below(10)).collect::<Vec<_>>()
+        };
+        assert_eq!(draw(0), draw(0));
+        assert_ne!(draw(0), draw(1));
+        assert!(draw(2).iter().all(|&n| n < 10));
+    }
+
+    #[test]
+    fn overflow_errors() {
+        assert_eq!(2u8.checked_mul(100).or_overflow("doubling"), Ok(200));
+        let err = 3u8.checked_mul(100).or_overflow("tripling").unwrap_err();
+        assert_eq!(err.to_string(), "integer overflow while tripling");
+        let answer: Result<u64, _> = Err(err);
+        assert!(answer.into_answer().is_err());
+    }
+}
@@ -1,11 +1,11 @@
-use std::{error::Error, fs::read_to_string, time::Instant};
+use std::error::Error;
+
+use common::Runner;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let input = read_to_string("input/day00.txt")?;
-    let now = Instant::now();
-    println!("p1: {} ({}µs)", part1(&input)?, now.elapsed().as_micros());
-    let now = Instant::now();
-    println!("p2: {} ({}µs)", part2(&input)?, now.elapsed().as_micros());
+    let runner = Runner::new(2024, 0)?;
+    runner.part(1, part1)?;
+    runner.part(2, part2)?;
     Ok(())
 }
 
@@ -1,9 +1,11 @@
-use std::{collections::HashMap, error::Error, fs::read_to_string, iter::zip};
+use std::{collections::HashMap, error::Error, iter::zip};
+
+use common::Runner;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let input = read_to_string("input/day01.txt")?;
-    println!("P1: {}", part1(&input)?);
-    println!("P2: {}", part2(&input)?);
+    let runner = Runner::new(2024, 1)?;
+    runner.part_with(1, parse, total_distance)?;
+    runner.part_with(2, parse, similarity_score)?;
     Ok(())
 }
 
@@ -20,25 +22,20 @@ fn parse(input: &str) -> Result<(Vec<usize>, Vec<usize>), Box<dyn Error>> {
     Ok((left, right))
 }
 
-fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
-    let (mut left, mut right) = parse(input)?;
+fn total_distance((mut left, mut right): (Vec<usize>, Vec<usize>)) -> usize {
     left.sort();
     right.sort();
-    let total_distance = zip(left, right).map(|(a, b)| a.abs_diff(b)).sum();
-    Ok(total_distance)
+    zip(left, right).map(|(a, b)| a.abs_diff(b)).sum()
 }
 
-fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
-    let (left, right) = parse(input)?;
+fn similarity_score((left, right): (Vec<usize>, Vec<usize>)) -> usize {
     let mut counts = HashMap::new();
     for n in right {
         *counts.entry(n).or_insert(0) += 1
     }
-    let similarity_score = left
-        .into_iter()
+    left.into_iter()
         .map(|n| counts.get(&n).copied().unwrap_or(0) * n)
-        .sum();
-    Ok(similarity_score)
+        .sum()
 }
 
 #[cfg(test)]
@@ -54,13 +51,13 @@ mod tests {
 
     #[test]
     fn part1_example() -> Result<(), Box<dyn Error>> {
-        assert_eq!(part1(EXAMPLE)?, 11);
+        assert_eq!(total_distance(parse(EXAMPLE)?), 11);
         Ok(())
     }
 
     #[test]
     fn part2_example() -> Result<(), Box<dyn Error>> {
-        assert_eq!(part2(EXAMPLE)?, 31);
+        assert_eq!(similarity_score(parse(EXAMPLE)?), 31);
         Ok(())
     }
 }
@@ -0,0 +1,330 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use common::Runner;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 2)?;
+    let policy = policy_from_args(&runner)?;
+    if runner.flag("--explain") {
+        eprint!("{}", explain(runner.input(), &policy)?);
+    }
+    runner.part(1, part1)?;
+    if policy == SafetyPolicy::PART2 {
+        runner.part(2, part2)?;
+    } else {
+        runner.part(2, |input| count_safe(input, &policy))?;
+    }
+    Ok(())
+}
+
+fn policy_from_args(runner: &Runner) -> Result<SafetyPolicy, Box<dyn Error>> {
+    let mut policy = SafetyPolicy::PART2;
+    if let Some(n) = runner.value("--min-step") {
+        policy.min_step = n.parse()?;
+    }
+    if let Some(n) = runner.value("--max-step") {
+        policy.max_step = n.parse()?;
+    }
+    if let Some(direction) = runner.value("--direction") {
+        policy.direction = direction.parse()?;
+    }
+    if let Some(n) = runner.value("--faults") {
+        policy.max_faults = n.parse()?;
+    }
+    policy.allow_plateaus |= runner.flag("--plateaus");
+    Ok(policy)
+}
+
+fn parse(line: &str) -> Result<Vec<usize>, Box<dyn Error>> {
+    let report = line
+        .split_ascii_whitespace()
+        .map(|s| s.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(report)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Direction {
+    Increasing,
+    Decreasing,
+    Monotonic,
+    Free,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "increasing" => Ok(Self::Increasing),
+            "decreasing" => Ok(Self::Decreasing),
+            "monotonic" => Ok(Self::Monotonic),
+            "free" => Ok(Self::Free),
+            _ => Err(format!("unknown direction {s:?}")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SafetyPolicy {
+    min_step: usize,
+    max_step: usize,
+    allow_plateaus: bool,
+    direction: Direction,
+    max_faults: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Fault {
+    DirectionChange,
+    Gap { max: usize },
+    Step { min: usize },
+    Plateau,
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fault::DirectionChange => write!(f, "direction change"),
+            Fault::Gap { max } => write!(f, "gap > {max}"),
+            Fault::Step { min } => write!(f, "gap < {min}"),
+            Fault::Plateau => write!(f, "plateau"),
+        }
+    }
+}
+
+impl SafetyPolicy {
+    const PART1: Self = Self {
+        min_step: 1,
+        max_step: 3,
+        allow_plateaus: false,
+        direction: Direction::Monotonic,
+        max_faults: 0,
+    };
+
+    const PART2: Self = Self {
+        max_faults: 1,
+        ..Self::PART1
+    };
+
+    /// Whether the step between two levels is allowed, going up if `increasing` is
+    /// `Some(true)`, down if `Some(false)`, or either way if `None`
+    fn safe_step(&self, prev: usize, level: usize, increasing: Option<bool>) -> bool {
+        if level == prev {
+            return self.allow_plateaus;
+        }
+        let step = level.abs_diff(prev);
+        (self.min_step..=self.max_step).contains(&step)
+            && increasing.is_none_or(|increasing| (level > prev) == increasing)
+    }
+
+    fn first_fault(&self, report: &[usize]) -> Option<(usize, Fault)> {
+        let mut increasing = match self.direction {
+            Direction::Increasing => Some(true),
+            Direction::Decreasing => Some(false),
+            Direction::Monotonic | Direction::Free => None,
+        };
+        report.windows(2).enumerate().find_map(|(i, pair)| {
+            let (prev, level) = (pair[0], pair[1]);
+            if level == prev {
+                return (!self.allow_plateaus).then_some((i, Fault::Plateau));
+            }
+            if self.direction != Direction::Free {
+                let up = level > prev;
+                if *increasing.get_or_insert(up) != up {
+                    return Some((i, Fault::DirectionChange));
+                }
+            }
+            let step = level.abs_diff(prev);
+            if step > self.max_step {
+                Some((i, Fault::Gap { max: self.max_step }))
+            } else if step < self.min_step {
+                Some((i, Fault::Step { min: self.min_step }))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn dampen(&self, report: &[usize]) -> Option<Vec<usize>> {
+        let directions: &[Option<bool>] = match self.direction {
+            Direction::Increasing => &[Some(true)],
+            Direction::Decreasing => &[Some(false)],
+            Direction::Monotonic => &[Some(true), Some(false)],
+            Direction::Free => &[None],
+        };
+        directions
+            .iter()
+            .filter_map(|&increasing| self.dampen_direction(report, increasing))
+            .min_by_key(|removed| removed.len())
+    }
+
+    /// A kept level only has to be compared with the previous kept level, which is at most
+    /// `max_faults + 1` positions back, so this is a single O(n·k) pass over the report.
+    fn dampen_direction(&self, report: &[usize], increasing: Option<bool>) -> Option<Vec<usize>> {
+        if report.is_empty() {
+            return Some(vec![]);
+        }
+        let n = report.len();
+        let max_faults = self.max_faults;
+
+        // removed[i] is the fewest levels removed before i when level i is kept. Ties prefer the
+        // nearest kept predecessor, so earlier levels are removed first as in the puzzle text.
+        let mut removed = vec![usize::MAX; n];
+        let mut kept_before = vec![None; n];
+        for i in 0..n {
+            if i <= max_faults {
+                removed[i] = i;
+            }
+            for j in i.saturating_sub(max_faults + 1)..i {
+                if removed[j] == usize::MAX || !self.safe_step(report[j], report[i], increasing) {
+                    continue;
+                }
+                let count = removed[j] + i - j - 1;
+                if count <= removed[i] {
+                    removed[i] = count;
+                    kept_before[i] = Some(j);
+                }
+            }
+        }
+
+        let (last, total) = (n.saturating_sub(max_faults + 1)..n)
+            .filter(|&i| removed[i] != usize::MAX)
+            .map(|i| (i, removed[i] + n - 1 - i))
+            .min_by_key(|&(_, total)| total)?;
+        if total > max_faults {
+            return None;
+        }
+
+        let mut kept = vec![false; n];
+        let mut i = Some(last);
+        while let Some(j) = i {
+            kept[j] = true;
+            i = kept_before[j];
+        }
+        Some((0..n).filter(|&i| !kept[i]).collect())
+    }
+}
+
+fn count_safe(input: &str, policy: &SafetyPolicy) -> Result<usize, Box<dyn Error>> {
+    let mut count = 0;
+    for line in input.lines() {
+        let report = parse(line)?;
+        if policy.dampen(&report).is_some() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+fn explain(input: &str, policy: &SafetyPolicy) -> Result<String, Box<dyn Error>> {
+    let mut out = String::new();
+    for line in input.lines() {
+        let report = parse(line)?;
+        let Some((i, fault)) = policy.first_fault(&report) else {
+            continue;
+        };
+        let (prev, level) = (report[i], report[i + 1]);
+        let fix = match policy.dampen(&report) {
+            Some(removed) => format!("safe after removing levels at {removed:?}"),
+            None => format!("not fixable with up to {} removals", policy.max_faults),
+        };
+        out += &format!("{line}: {prev} -> {level} at index {i}: {fault}, {fix}\n");
+    }
+    Ok(out)
+}
+
+fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    count_safe(input, &SafetyPolicy::PART1)
+}
+
+fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    count_safe(input, &SafetyPolicy::PART2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    #[test]
+    fn part1_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part1(EXAMPLE)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part2(EXAMPLE)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn dampen_removed_levels() {
+        let policy = SafetyPolicy::PART2;
+        assert_eq!(policy.dampen(&[7, 6, 4, 2, 1]), Some(vec![]));
+        assert_eq!(policy.dampen(&[1, 3, 2, 4, 5]), Some(vec![1]));
+        assert_eq!(policy.dampen(&[8, 6, 4, 4, 1]), Some(vec![2]));
+        assert_eq!(policy.dampen(&[1, 2, 7, 8, 9]), None);
+
+        let policy = SafetyPolicy {
+            max_faults: 2,
+            ..policy
+        };
+        assert_eq!(policy.dampen(&[9, 1, 2, 3, 9, 4]), Some(vec![0, 4]));
+        assert_eq!(policy.dampen(&[1, 9, 9, 2, 3]), Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn custom_policy() {
+        let policy = SafetyPolicy {
+            min_step: 2,
+            max_step: 5,
+            allow_plateaus: true,
+            direction: Direction::Free,
+            max_faults: 0,
+        };
+        assert_eq!(policy.first_fault(&[1, 6, 6, 2, 4]), None);
+        assert_eq!(
+            policy.first_fault(&[1, 6, 7]),
+            Some((1, Fault::Step { min: 2 }))
+        );
+        assert_eq!(policy.dampen(&[1, 7]), None);
+
+        let policy = SafetyPolicy {
+            direction: Direction::Decreasing,
+            ..SafetyPolicy::PART1
+        };
+        assert_eq!(
+            policy.first_fault(&[1, 2]),
+            Some((0, Fault::DirectionChange))
+        );
+        assert_eq!(policy.dampen(&[9, 7, 6]), Some(vec![]));
+    }
+
+    #[test]
+    fn explain_faults() -> Result<(), Box<dyn Error>> {
+        let explained = explain(EXAMPLE, &SafetyPolicy::PART2)?;
+        let lines = explained.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "1 2 7 8 9: 2 -> 7 at index 1: gap > 3, not fixable with up to 1 removals"
+        );
+        assert_eq!(
+            lines[2],
+            "1 3 2 4 5: 3 -> 2 at index 1: direction change, safe after removing levels at [1]"
+        );
+        assert_eq!(
+            lines[3],
+            "8 6 4 4 1: 4 -> 4 at index 2: plateau, safe after removing levels at [2]"
+        );
+        Ok(())
+    }
+}
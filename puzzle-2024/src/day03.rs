@@ -0,0 +1,574 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+};
+
+use common::{bench, Runner};
+use regex::Regex;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 3)?;
+    if runner.flag("--trace") {
+        let mut interpreter = Interpreter::with_conditionals().traced();
+        interpreter.run(runner.input().as_bytes())?;
+        for step in interpreter.trace.iter().flatten() {
+            eprintln!("{step}");
+        }
+    }
+    if runner.flag("--bench") {
+        let input = runner.input();
+        bench("part1 interpreter", 100, || part1(input));
+        bench("part1 scanner", 100, || scan(input, false));
+        bench("part1 regex", 100, || part1_regex(input));
+        bench("part2 interpreter", 100, || part2(input));
+        bench("part2 scanner", 100, || scan(input, true));
+        bench("part2 regex", 100, || part2_regex(input));
+    }
+    runner.part(1, part1)?;
+    runner.part(2, part2)?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Instruction {
+    Mul(usize, usize),
+    Do,
+    Dont,
+    Custom { name: String, args: Vec<usize> },
+}
+
+impl Instruction {
+    fn new(name: &str, args: Vec<usize>) -> Self {
+        match (name, args.as_slice()) {
+            ("mul", &[a, b]) => Self::Mul(a, b),
+            ("do", []) => Self::Do,
+            ("don't", []) => Self::Dont,
+            _ => Self::Custom {
+                name: name.to_string(),
+                args,
+            },
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Mul(..) => "mul",
+            Self::Do => "do",
+            Self::Dont => "don't",
+            Self::Custom { name, .. } => name,
+        }
+    }
+
+    fn args(&self) -> Vec<usize> {
+        match self {
+            Self::Mul(a, b) => vec![*a, *b],
+            Self::Do | Self::Dont => vec![],
+            Self::Custom { args, .. } => args.clone(),
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mul(a, b) => write!(f, "mul({a},{b})"),
+            Self::Do => write!(f, "do()"),
+            Self::Dont => write!(f, "don't()"),
+            Self::Custom { name, args } => {
+                let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+                write!(f, "{name}({})", args.join(","))
+            }
+        }
+    }
+}
+
+/// The syntax of an instruction: `name(a,b,...)` with `arity` operands of 1-3 digits
+struct Spec {
+    name: String,
+    arity: usize,
+}
+
+enum Scan {
+    Token(usize, Vec<usize>),
+    NoMatch,
+    NeedMore,
+}
+
+impl Spec {
+    fn new(name: &str, arity: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            arity,
+        }
+    }
+
+    /// Try to match the instruction at the start of `bytes`, which may end in the middle
+    /// of an instruction when more input is still to be read
+    fn scan(&self, bytes: &[u8]) -> Scan {
+        let mut i = 0;
+        let expect = |i: &mut usize, expected: u8| match bytes.get(*i) {
+            None => Err(Scan::NeedMore),
+            Some(&b) if b == expected => {
+                *i += 1;
+                Ok(())
+            }
+            Some(_) => Err(Scan::NoMatch),
+        };
+        let mut args = Vec::with_capacity(self.arity);
+        let mut scan = || {
+            for &b in self.name.as_bytes() {
+                expect(&mut i, b)?;
+            }
+            expect(&mut i, b'(')?;
+            for k in 0..self.arity {
+                if k > 0 {
+                    expect(&mut i, b',')?;
+                }
+                let start = i;
+                let mut n = 0;
+                while i - start < 3 {
+                    match bytes.get(i) {
+                        None => return Err(Scan::NeedMore),
+                        Some(b) if b.is_ascii_digit() => n = n * 10 + (b - b'0') as usize,
+                        Some(_) => break,
+                    }
+                    i += 1;
+                }
+                if i == start {
+                    return Err(Scan::NoMatch);
+                }
+                args.push(n);
+            }
+            expect(&mut i, b')')
+        };
+        match scan() {
+            Ok(()) => Scan::Token(i, args),
+            Err(scan) => scan,
+        }
+    }
+}
+
+/// Splits a stream of corrupted memory into instructions, holding only a partially
+/// matched instruction in memory between reads
+struct Lexer<'a, R> {
+    reader: R,
+    specs: &'a [Spec],
+    window: Vec<u8>,
+    pos: usize,
+    offset: usize,
+    eof: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Token {
+    offset: usize,
+    instruction: Instruction,
+}
+
+impl<'a, R: BufRead> Lexer<'a, R> {
+    fn new(reader: R, specs: &'a [Spec]) -> Self {
+        Self {
+            reader,
+            specs,
+            window: vec![],
+            pos: 0,
+            offset: 0,
+            eof: false,
+        }
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        self.window.drain(..self.pos);
+        self.pos = 0;
+        let buf = self.reader.fill_buf()?;
+        self.eof = buf.is_empty();
+        self.window.extend_from_slice(buf);
+        let len = buf.len();
+        self.reader.consume(len);
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for Lexer<'_, R> {
+    type Item = io::Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.window[self.pos..];
+            if rest.is_empty() {
+                if self.eof {
+                    return None;
+                }
+                if let Err(e) = self.fill() {
+                    return Some(Err(e));
+                }
+                continue;
+            }
+
+            let mut need_more = false;
+            for spec in self.specs {
+                match spec.scan(rest) {
+                    Scan::Token(len, args) => {
+                        let token = Token {
+                            offset: self.offset,
+                            instruction: Instruction::new(&spec.name, args),
+                        };
+                        self.pos += len;
+                        self.offset += len;
+                        return Some(Ok(token));
+                    }
+                    Scan::NeedMore => need_more = true,
+                    Scan::NoMatch => (),
+                }
+            }
+            if need_more && !self.eof {
+                if let Err(e) = self.fill() {
+                    return Some(Err(e));
+                }
+            } else {
+                self.pos += 1;
+                self.offset += 1;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    enabled: bool,
+    sum: usize,
+}
+
+/// Executes an instruction with its operands, returning false if it was skipped
+type Handler = Box<dyn Fn(&mut State, &[usize]) -> bool>;
+
+#[derive(Debug)]
+struct Step {
+    token: Token,
+    executed: bool,
+    sum: usize,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Step {
+            token,
+            executed,
+            sum,
+        } = self;
+        let status = if *executed { "" } else { " (skipped)" };
+        write!(
+            f,
+            "{:>8}: {}{status} sum={sum}",
+            token.offset, token.instruction
+        )
+    }
+}
+
+struct Interpreter {
+    specs: Vec<Spec>,
+    handlers: HashMap<String, Handler>,
+    state: State,
+    /// Every token executed and the sum after it, kept only for interpreters that are `traced`
+    trace: Option<Vec<Step>>,
+}
+
+impl Interpreter {
+    fn new() -> Self {
+        let mut interpreter = Self {
+            specs: vec![],
+            handlers: HashMap::new(),
+            state: State {
+                enabled: true,
+                sum: 0,
+            },
+            trace: None,
+        };
+        interpreter.register("mul", 2, |state, args| {
+            if state.enabled {
+                state.sum += args[0] * args[1];
+            }
+            state.enabled
+        });
+        interpreter
+    }
+
+    fn with_conditionals() -> Self {
+        let mut interpreter = Self::new();
+        interpreter.register("do", 0, |state, _| {
+            state.enabled = true;
+            true
+        });
+        interpreter.register("don't", 0, |state, _| {
+            state.enabled = false;
+            true
+        });
+        interpreter
+    }
+
+    fn traced(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    fn register(
+        &mut self,
+        name: &str,
+        arity: usize,
+        handler: impl Fn(&mut State, &[usize]) -> bool + 'static,
+    ) {
+        self.specs.push(Spec::new(name, arity));
+        self.handlers.insert(name.to_string(), Box::new(handler));
+    }
+
+    fn execute(&mut self, token: Token) {
+        let instruction = &token.instruction;
+        let executed = self
+            .handlers
+            .get(instruction.name())
+            .is_some_and(|handler| handler(&mut self.state, &instruction.args()));
+        if let Some(trace) = &mut self.trace {
+            trace.push(Step {
+                token,
+                executed,
+                sum: self.state.sum,
+            });
+        }
+    }
+
+    fn run(&mut self, reader: impl BufRead) -> io::Result<usize> {
+        let specs = std::mem::take(&mut self.specs);
+        for token in Lexer::new(reader, &specs) {
+            self.execute(token?);
+        }
+        self.specs = specs;
+        Ok(self.state.sum)
+    }
+}
+
+fn scan_operand(bytes: &[u8]) -> Option<(usize, usize)> {
+    let len = bytes
+        .iter()
+        .take(3)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let n = bytes[..len]
+        .iter()
+        .fold(0, |n, b| n * 10 + (b - b'0') as usize);
+    (len > 0).then_some((n, len))
+}
+
+fn scan_mul(bytes: &[u8]) -> Option<(usize, usize)> {
+    let rest = bytes.strip_prefix(b"mul(")?;
+    let (a, a_len) = scan_operand(rest)?;
+    let rest = rest[a_len..].strip_prefix(b",")?;
+    let (b, b_len) = scan_operand(rest)?;
+    (rest.get(b_len) == Some(&b')')).then_some((a * b, 4 + a_len + 1 + b_len + 1))
+}
+
+/// A single left-to-right pass over the bytes without the interpreter's lexer, kept as a
+/// baseline for `--bench`
+fn scan(input: &str, conditionals: bool) -> usize {
+    let bytes = input.as_bytes();
+    let mut enabled = true;
+    let mut sum = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        match rest[0] {
+            b'm' => {
+                if let Some((product, len)) = scan_mul(rest) {
+                    if enabled {
+                        sum += product;
+                    }
+                    i += len;
+                    continue;
+                }
+            }
+            b'd' if conditionals => {
+                if rest.starts_with(b"do()") {
+                    enabled = true;
+                    i += 4;
+                    continue;
+                } else if rest.starts_with(b"don't()") {
+                    enabled = false;
+                    i += 7;
+                    continue;
+                }
+            }
+            _ => (),
+        }
+        i += 1;
+    }
+    sum
+}
+
+fn part1(input: &str) -> io::Result<usize> {
+    Interpreter::new().run(input.as_bytes())
+}
+
+fn part2(input: &str) -> io::Result<usize> {
+    Interpreter::with_conditionals().run(input.as_bytes())
+}
+
+fn part1_regex(input: &str) -> Result<usize, Box<dyn Error>> {
+    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)")?;
+    let sum = re
+        .captures_iter(input)
+        .map(|c| {
+            let (_, [a, b]) = c.extract();
+            let a = a.parse::<usize>()?;
+            let b = b.parse::<usize>()?;
+            Ok(a * b)
+        })
+        .sum::<Result<usize, Box<dyn Error>>>()?;
+    Ok(sum)
+}
+
+fn part2_regex(input: &str) -> Result<usize, Box<dyn Error>> {
+    let re = Regex::new(r"(?:(do(?:n't)?)\(\))|(?:(mul)\((\d{1,3}),(\d{1,3})\))")?;
+    let mut enabled = true;
+    let mut sum = 0;
+    for c in re.captures_iter(input) {
+        let mut matches = c.iter().skip(1).filter_map(|m| m.map(|m| m.as_str()));
+        match matches.next() {
+            Some("do") => enabled = true,
+            Some("don't") => enabled = false,
+            Some("mul") if enabled => {
+                sum += matches
+                    .flat_map(|s| s.parse::<usize>().ok())
+                    .product::<usize>();
+            }
+            _ => (),
+        };
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Rng;
+
+    const EXAMPLE1: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+    const EXAMPLE2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn part1_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part1(EXAMPLE1)?, 161);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part2(EXAMPLE2)?, 48);
+        Ok(())
+    }
+
+    #[test]
+    fn interpreter_matches_baselines() -> Result<(), Box<dyn Error>> {
+        const PIECES: [&str; 20] = [
+            "mul(2,4)",
+            "mul(123,45)",
+            "mul(1234,5)",
+            "mul(",
+            "mul",
+            "do()",
+            "don't()",
+            "do(",
+            "don't",
+            "(",
+            ")",
+            ",",
+            "m",
+            "d",
+            "x",
+            " ",
+            "1",
+            "23",
+            "456",
+            "7,8)",
+        ];
+        let mut rng = Rng::new(3);
+        let mut total = 0;
+        for _ in 0..300 {
+            let memory = (0..rng.below(100))
+                .map(|_| PIECES[rng.below(PIECES.len())])
+                .collect::<String>();
+            let sum = part2(&memory)?;
+            assert_eq!(part1(&memory)?, part1_regex(&memory)?, "{memory}");
+            assert_eq!(part1(&memory)?, scan(&memory, false), "{memory}");
+            assert_eq!(sum, part2_regex(&memory)?, "{memory}");
+            assert_eq!(sum, scan(&memory, true), "{memory}");
+            total += sum;
+        }
+        assert!(total > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn interpreter_examples() -> Result<(), Box<dyn Error>> {
+        assert_eq!(Interpreter::new().run(EXAMPLE1.as_bytes())?, 161);
+        let mut interpreter = Interpreter::with_conditionals();
+        assert_eq!(interpreter.run(EXAMPLE2.as_bytes())?, 48);
+        assert!(interpreter.trace.is_none());
+        let mut interpreter = Interpreter::with_conditionals().traced();
+        assert_eq!(interpreter.run(EXAMPLE2.as_bytes())?, 48);
+        let trace = interpreter
+            .trace
+            .iter()
+            .flatten()
+            .map(|step| (step.token.offset, step.executed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            trace,
+            [
+                (1, true),
+                (20, true),
+                (28, false),
+                (48, false),
+                (59, true),
+                (64, true)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lexer_streams_small_chunks() -> Result<(), Box<dyn Error>> {
+        let specs = [
+            Spec::new("mul", 2),
+            Spec::new("do", 0),
+            Spec::new("don't", 0),
+        ];
+        let whole = Lexer::new(EXAMPLE2.as_bytes(), &specs).collect::<Result<Vec<_>, _>>()?;
+        let reader = io::BufReader::with_capacity(3, EXAMPLE2.as_bytes());
+        let chunked = Lexer::new(reader, &specs).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(whole, chunked);
+        assert_eq!(chunked[1].instruction, Instruction::Dont);
+        Ok(())
+    }
+
+    #[test]
+    fn operand_limit_and_custom_instructions() -> Result<(), Box<dyn Error>> {
+        let mut interpreter = Interpreter::new().traced();
+        interpreter.register("add", 2, |state, args| {
+            state.sum += args[0] + args[1];
+            true
+        });
+        let sum = interpreter.run("mul(1234,2)mul(123,2)add(1,2)add(1,2,3)mul(4,5".as_bytes())?;
+        assert_eq!(sum, 249);
+        assert_eq!(
+            interpreter.trace.unwrap_or_default()[1].token.instruction,
+            Instruction::Custom {
+                name: "add".into(),
+                args: vec![1, 2]
+            }
+        );
+        Ok(())
+    }
+}
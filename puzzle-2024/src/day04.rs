@@ -0,0 +1,361 @@
+use std::{collections::HashSet, error::Error, str::FromStr};
+
+use common::Runner;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 4)?;
+    if let Some(style) = runner.value("--render") {
+        let filter = MatchFilter {
+            start: runner.coord("--start")?,
+            dir: runner.value("--dir").map(parse_dir).transpose()?,
+        };
+        let x_mas = runner.value("--part") == Some("2");
+        eprint!(
+            "{}",
+            render_matches(runner.input(), x_mas, &filter, style.parse()?)
+        );
+    }
+    runner.part(1, part1)?;
+    runner.part(2, part2)?;
+    Ok(())
+}
+
+const DELTAS: [(i32, i32); 8] = [
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+];
+
+/// Names for `--dir`, in the same order as `DELTAS`
+const DIR_NAMES: [&str; 8] = ["w", "nw", "n", "ne", "e", "se", "s", "sw"];
+
+const WILDCARD: u8 = b'.';
+
+struct Grid {
+    cells: Vec<Vec<u8>>,
+    height: i32,
+    width: i32,
+}
+
+impl Grid {
+    fn parse(input: &str) -> Self {
+        let cells = input
+            .lines()
+            .map(|line| line.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+        let (height, width) = (cells.len() as i32, cells[0].len() as i32);
+        Self {
+            cells,
+            height,
+            width,
+        }
+    }
+
+    fn get(&self, y: i32, x: i32) -> Option<u8> {
+        (y >= 0 && y < self.height && x >= 0 && x < self.width)
+            .then(|| self.cells[y as usize][x as usize])
+    }
+
+    fn coords(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        (0..self.height).flat_map(|y| (0..self.width).map(move |x| (y, x)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct WordMatch {
+    word: usize,
+    start: (i32, i32),
+    dir: (i32, i32),
+}
+
+fn find_words(grid: &Grid, words: &[&str]) -> Vec<WordMatch> {
+    let mut found = vec![];
+    for (y0, x0) in grid.coords() {
+        for (i, word) in words.iter().enumerate() {
+            for (dy, dx) in DELTAS {
+                let matched = word
+                    .bytes()
+                    .zip(0..)
+                    .all(|(c, k)| grid.get(y0 + k * dy, x0 + k * dx) == Some(c));
+                if matched {
+                    found.push(WordMatch {
+                        word: i,
+                        start: (y0, x0),
+                        dir: (dy, dx),
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Stencil {
+    rows: Vec<Vec<u8>>,
+}
+
+impl Stencil {
+    fn parse(pattern: &str) -> Self {
+        Self {
+            rows: pattern
+                .lines()
+                .map(|line| line.as_bytes().to_vec())
+                .collect(),
+        }
+    }
+
+    fn rotate(&self) -> Self {
+        let (h, w) = (self.rows.len(), self.rows[0].len());
+        Self {
+            rows: (0..w)
+                .map(|x| (0..h).rev().map(|y| self.rows[y][x]).collect())
+                .collect(),
+        }
+    }
+
+    fn reflect(&self) -> Self {
+        Self {
+            rows: self
+                .rows
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        }
+    }
+
+    fn variants(&self) -> Vec<Stencil> {
+        let mut variants: Vec<Stencil> = vec![];
+        let mut stencil = self.clone();
+        for _ in 0..4 {
+            for variant in [stencil.reflect(), stencil.clone()] {
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+            }
+            stencil = stencil.rotate();
+        }
+        variants
+    }
+
+    fn matches_at(&self, grid: &Grid, (y0, x0): (i32, i32)) -> bool {
+        self.rows.iter().zip(y0..).all(|(row, y)| {
+            row.iter()
+                .zip(x0..)
+                .all(|(&c, x)| grid.get(y, x).is_some_and(|g| c == WILDCARD || c == g))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct StencilMatch {
+    variant: usize,
+    start: (i32, i32),
+}
+
+fn find_stencil(grid: &Grid, stencil: &Stencil) -> Vec<StencilMatch> {
+    let variants = stencil.variants();
+    grid.coords()
+        .flat_map(|start| {
+            variants
+                .iter()
+                .enumerate()
+                .filter(move |(_, v)| v.matches_at(grid, start))
+                .map(move |(variant, _)| StencilMatch { variant, start })
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Style {
+    Ansi,
+    Dots,
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ansi" => Ok(Self::Ansi),
+            "dots" => Ok(Self::Dots),
+            _ => Err(format!("unknown render style {s:?}, expected ansi or dots")),
+        }
+    }
+}
+
+#[derive(Default)]
+struct MatchFilter {
+    start: Option<(i32, i32)>,
+    dir: Option<(i32, i32)>,
+}
+
+fn parse_dir(s: &str) -> Result<(i32, i32), Box<dyn Error>> {
+    let i = DIR_NAMES
+        .iter()
+        .position(|&name| name.eq_ignore_ascii_case(s))
+        .ok_or_else(|| format!("unknown direction {s:?}, expected one of {DIR_NAMES:?}"))?;
+    Ok(DELTAS[i])
+}
+
+fn render_matches(input: &str, x_mas: bool, filter: &MatchFilter, style: Style) -> String {
+    let grid = Grid::parse(input);
+    let mut cells = HashSet::new();
+    if x_mas {
+        let stencil = Stencil::parse(X_MAS);
+        let variants = stencil.variants();
+        for m in find_stencil(&grid, &stencil) {
+            if filter.start.is_some_and(|start| start != m.start) {
+                continue;
+            }
+            let (y0, x0) = m.start;
+            for (row, y) in variants[m.variant].rows.iter().zip(y0..) {
+                for (&c, x) in row.iter().zip(x0..) {
+                    if c != WILDCARD {
+                        cells.insert((y, x));
+                    }
+                }
+            }
+        }
+    } else {
+        for m in find_words(&grid, &[XMAS]) {
+            if filter.start.is_some_and(|start| start != m.start)
+                || filter.dir.is_some_and(|dir| dir != m.dir)
+            {
+                continue;
+            }
+            let ((y0, x0), (dy, dx)) = (m.start, m.dir);
+            cells.extend((0..XMAS.len() as i32).map(|k| (y0 + k * dy, x0 + k * dx)));
+        }
+    }
+    render(&grid, &cells, style)
+}
+
+fn render(grid: &Grid, cells: &HashSet<(i32, i32)>, style: Style) -> String {
+    let mut out = String::new();
+    for (y, row) in grid.cells.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            let c = c as char;
+            match (style, cells.contains(&(y as i32, x as i32))) {
+                (Style::Ansi, true) => out += &format!("\x1b[1;31m{c}\x1b[0m"),
+                (Style::Dots, false) => out.push('.'),
+                _ => out.push(c),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+const XMAS: &str = "XMAS";
+
+fn part1(input: &str) -> usize {
+    find_words(&Grid::parse(input), &[XMAS]).len()
+}
+
+const X_MAS: &str = "M.S\n.A.\nM.S";
+
+fn part2(input: &str) -> usize {
+    find_stencil(&Grid::parse(input), &Stencil::parse(X_MAS)).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), 9);
+    }
+
+    #[test]
+    fn word_matches() {
+        let grid = Grid::parse("XMAS\nMM..\nA.A.\nS..S");
+        let found = find_words(&grid, &["XMAS", "SAM"]);
+        let starts = found
+            .iter()
+            .map(|m| (m.word, m.start, m.dir))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            starts,
+            [
+                (0, (0, 0), (0, 1)),
+                (0, (0, 0), (1, 1)),
+                (0, (0, 0), (1, 0)),
+                (1, (0, 3), (0, -1)),
+                (1, (3, 0), (-1, 0)),
+                (1, (3, 3), (-1, -1))
+            ]
+        );
+    }
+
+    #[test]
+    fn stencil_variants() {
+        assert_eq!(Stencil::parse(X_MAS).variants().len(), 4);
+        let l = Stencil::parse("X.\nXX");
+        assert_eq!(l.variants().len(), 4);
+        let f = Stencil::parse("AB\n.C");
+        assert_eq!(f.variants().len(), 8);
+        assert_eq!(Stencil::parse("AB").rotate(), Stencil::parse("A\nB"));
+    }
+
+    #[test]
+    fn render_like_puzzle() -> Result<(), Box<dyn Error>> {
+        let all = MatchFilter::default();
+        let xmas = "....XXMAS.
+.SAMXMS...
+...S..A...
+..A.A.MS.X
+XMASAMX.MM
+X.....XA.A
+S.S.S.S.SS
+.A.A.A.A.A
+..M.M.M.MM
+.X.X.XMASX
+";
+        assert_eq!(render_matches(EXAMPLE, false, &all, Style::Dots), xmas);
+        let x_mas = ".M.S......
+..A..MSMS.
+.M.S.MAA..
+..A.ASMSM.
+.M.S.M....
+..........
+S.S.S.S.S.
+.A.A.A.A..
+M.M.M.M.M.
+..........
+";
+        assert_eq!(render_matches(EXAMPLE, true, &all, Style::Dots), x_mas);
+
+        let filter = MatchFilter {
+            start: Some((9, 5)),
+            dir: Some(parse_dir("E")?),
+        };
+        let rendered = render_matches(EXAMPLE, false, &filter, Style::Ansi);
+        let red = |c| format!("\x1b[1;31m{c}\x1b[0m");
+        let last_row = format!("MXMXA{}{}{}{}X\n", red('X'), red('M'), red('A'), red('S'));
+        assert!(rendered.ends_with(&last_row));
+        Ok(())
+    }
+}
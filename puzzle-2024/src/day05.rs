@@ -0,0 +1,444 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+};
+
+use common::Runner;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 5)?;
+    if runner.flag("--explain") {
+        eprint!("{}", explain(runner.input()));
+    }
+    if runner.flag("--summary") {
+        eprint!("{}", summary(runner.input(), 10));
+    }
+    runner.part(1, part1)?;
+    runner.part(2, part2)?;
+    Ok(())
+}
+
+struct Rules {
+    after: HashMap<usize, HashSet<usize>>,
+}
+
+impl Rules {
+    fn must_precede(&self, a: usize, b: usize) -> bool {
+        self.after.get(&a).is_some_and(|after| after.contains(&b))
+    }
+
+    /// Every rule whose pages both appear in the update in the wrong order, by position of
+    /// the page that should have come later
+    fn violations(&self, update: &[usize]) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (after_index, &after) in update.iter().enumerate() {
+            for (before_index, &before) in update.iter().enumerate().skip(after_index + 1) {
+                if self.must_precede(before, after) {
+                    violations.push(Violation {
+                        before,
+                        after,
+                        before_index,
+                        after_index,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    fn in_right_order(&self, update: &[usize]) -> bool {
+        let positions = update
+            .iter()
+            .enumerate()
+            .map(|(i, &page)| (page, i))
+            .collect::<HashMap<_, _>>();
+        update.iter().enumerate().all(|(i, page)| {
+            self.after.get(page).is_none_or(|after| {
+                after
+                    .iter()
+                    .filter_map(|p| positions.get(p))
+                    .all(|&j| j > i)
+            })
+        })
+    }
+
+    /// Order the pages of an update with Kahn's algorithm over the rules between them,
+    /// keeping unconstrained pages in their original order
+    fn sort(&self, update: &[usize]) -> Result<Vec<usize>, CycleError> {
+        let n = update.len();
+        let mut successors = vec![vec![]; n];
+        let mut in_degree = vec![0; n];
+        for i in 0..n {
+            for j in 0..n {
+                if self.must_precede(update[i], update[j]) {
+                    successors[i].push(j);
+                    in_degree[j] += 1;
+                }
+            }
+        }
+
+        let mut ready = (0..n)
+            .filter(|&i| in_degree[i] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut sorted = Vec::with_capacity(n);
+        while let Some(Reverse(i)) = ready.pop() {
+            sorted.push(update[i]);
+            for &j in &successors[i] {
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    ready.push(Reverse(j));
+                }
+            }
+        }
+        if sorted.len() == n {
+            return Ok(sorted);
+        }
+
+        // Every page left over has a predecessor that is also left over, so walking
+        // backwards through predecessors must eventually revisit a page
+        let mut path = vec![(0..n).find(|&i| in_degree[i] > 0).unwrap_or_default()];
+        loop {
+            let last = path[path.len() - 1];
+            let Some(prev) = (0..n).find(|&j| in_degree[j] > 0 && successors[j].contains(&last))
+            else {
+                break;
+            };
+            if let Some(start) = path.iter().position(|&i| i == prev) {
+                path.drain(..start);
+                break;
+            }
+            path.push(prev);
+        }
+        let mut cycle = path
+            .into_iter()
+            .rev()
+            .map(|i| update[i])
+            .collect::<Vec<_>>();
+        cycle.push(cycle[0]);
+        Err(CycleError {
+            update: update.to_vec(),
+            cycle,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct CycleError {
+    update: Vec<usize>,
+    /// Pages forming the cycle, with the first page repeated at the end
+    cycle: Vec<usize>,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |pages: &[usize], sep| {
+            pages
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        write!(
+            f,
+            "rules form a cycle {} in update {}",
+            join(&self.cycle, " -> "),
+            join(&self.update, ",")
+        )
+    }
+}
+
+impl Error for CycleError {}
+
+#[derive(Debug, PartialEq)]
+struct Violation {
+    before: usize,
+    after: usize,
+    before_index: usize,
+    after_index: usize,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{} violated: {} at index {}, {} at index {}",
+            self.before, self.after, self.after, self.after_index, self.before, self.before_index
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Move {
+    page: usize,
+    from: usize,
+    to: usize,
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from index {} to index {}",
+            self.page, self.from, self.to
+        )
+    }
+}
+
+/// The fewest moves turning an update into its sorted order. Pages in a longest subsequence
+/// that is already in sorted order stay put, every other page is moved once, in sorted
+/// order, to just after the page that precedes it.
+fn fix_moves(update: &[usize], sorted: &[usize]) -> Vec<Move> {
+    let positions = sorted
+        .iter()
+        .map(|page| update.iter().position(|p| p == page).unwrap_or_default())
+        .collect::<Vec<_>>();
+
+    // Longest increasing run of positions, so O(n²) is plenty for updates of a few dozen pages
+    let n = sorted.len();
+    let mut length = vec![1; n];
+    let mut prev = vec![None; n];
+    for i in 0..n {
+        for j in 0..i {
+            if positions[j] < positions[i] && length[j] + 1 > length[i] {
+                length[i] = length[j] + 1;
+                prev[i] = Some(j);
+            }
+        }
+    }
+    let mut keep = vec![false; n];
+    let mut i = (0..n).max_by_key(|&i| (length[i], Reverse(i)));
+    while let Some(j) = i {
+        keep[j] = true;
+        i = prev[j];
+    }
+
+    let mut current = update.to_vec();
+    let mut moves = vec![];
+    for (k, &page) in sorted.iter().enumerate() {
+        if keep[k] {
+            continue;
+        }
+        let from = current.iter().position(|&p| p == page).unwrap_or_default();
+        current.remove(from);
+        let to = match k {
+            0 => 0,
+            _ => {
+                current
+                    .iter()
+                    .position(|&p| p == sorted[k - 1])
+                    .unwrap_or_default()
+                    + 1
+            }
+        };
+        current.insert(to, page);
+        moves.push(Move { page, from, to });
+    }
+    moves
+}
+
+fn explain(input: &str) -> String {
+    let (rules, updates) = parse(input);
+    let mut out = String::new();
+    for update in updates {
+        let violations = rules.violations(&update);
+        if violations.is_empty() {
+            continue;
+        }
+        let pages = update
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        out += &format!("{pages}\n");
+        for violation in violations {
+            out += &format!("  {violation}\n");
+        }
+        match rules.sort(&update) {
+            Ok(sorted) => {
+                let moves = fix_moves(&update, &sorted);
+                let plural = if moves.len() == 1 { "" } else { "s" };
+                out += &format!("  fixed in {} move{plural}:\n", moves.len());
+                for m in moves {
+                    out += &format!("    {m}\n");
+                }
+            }
+            Err(e) => out += &format!("  not fixable: {e}\n"),
+        }
+    }
+    out
+}
+
+fn summary(input: &str, top: usize) -> String {
+    let (rules, updates) = parse(input);
+    let mut counts = HashMap::<(usize, usize), usize>::new();
+    for update in &updates {
+        for violation in rules.violations(update) {
+            *counts
+                .entry((violation.before, violation.after))
+                .or_default() += 1;
+        }
+    }
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort_unstable_by_key(|&(rule, count)| (Reverse(count), rule));
+    counts
+        .into_iter()
+        .take(top)
+        .map(|((before, after), count)| {
+            let plural = if count == 1 { "" } else { "s" };
+            format!("{before}|{after} violated by {count} update{plural}\n")
+        })
+        .collect()
+}
+
+fn parse(input: &str) -> (Rules, Vec<Vec<usize>>) {
+    let sections = input.split("\n\n").collect::<Vec<_>>();
+    let after = sections[0]
+        .lines()
+        .map(|line| {
+            line.split("|")
+                .filter_map(|s| s.parse::<usize>().ok())
+                .collect::<Vec<_>>()
+        })
+        .fold(HashMap::new(), |mut acc, rule| {
+            let (before, after) = (rule[0], rule[1]);
+            acc.entry(before).or_insert(HashSet::new()).insert(after);
+            acc
+        });
+    let updates = sections[1]
+        .lines()
+        .map(|line| {
+            line.split(",")
+                .filter_map(|s| s.parse::<usize>().ok())
+                .collect()
+        })
+        .collect();
+    (Rules { after }, updates)
+}
+
+fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    let (rules, updates) = parse(input);
+    let sum = updates
+        .into_iter()
+        .filter(|update| rules.in_right_order(update))
+        .map(|update| update[update.len() / 2])
+        .sum();
+    Ok(sum)
+}
+
+fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    let (rules, updates) = parse(input);
+    let mut sum = 0;
+    for update in updates {
+        if !rules.in_right_order(&update) {
+            let fixed = rules.sort(&update)?;
+            sum += fixed[fixed.len() / 2];
+        }
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    #[test]
+    fn part1_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part1(EXAMPLE)?, 143);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part2(EXAMPLE)?, 123);
+        Ok(())
+    }
+
+    #[test]
+    fn sort_respects_rules() {
+        let (rules, _) = parse(EXAMPLE);
+        assert_eq!(
+            rules.sort(&[75, 97, 47, 61, 53]).ok(),
+            Some(vec![97, 75, 47, 61, 53])
+        );
+        assert_eq!(
+            rules.sort(&[97, 13, 75, 29, 47]).ok(),
+            Some(vec![97, 75, 47, 29, 13])
+        );
+    }
+
+    #[test]
+    fn explain_violations() {
+        let (rules, _) = parse(EXAMPLE);
+        let update = [61, 13, 29];
+        let violations = rules.violations(&update);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].to_string(),
+            "29|13 violated: 13 at index 1, 29 at index 2"
+        );
+
+        let update = [97, 13, 75, 29, 47];
+        let sorted = rules.sort(&update).unwrap();
+        let moves = fix_moves(&update, &sorted);
+        assert_eq!(moves.len(), 2);
+        let mut fixed = update.to_vec();
+        for m in moves {
+            let page = fixed.remove(m.from);
+            fixed.insert(m.to, page);
+        }
+        assert_eq!(fixed, sorted);
+
+        let explained = explain(EXAMPLE);
+        assert!(explained
+            .starts_with("75,97,47,61,53\n  97|75 violated: 75 at index 0, 97 at index 1\n"));
+        assert!(explained.contains("  fixed in 1 move:\n    move 75 from index 0 to index 1\n"));
+        assert_eq!(summary(EXAMPLE, 1), "29|13 violated by 2 updates\n");
+    }
+
+    #[test]
+    fn cycle_in_update() {
+        let (rules, _) = parse("1|2\n2|3\n3|1\n\n1,2");
+        assert!(rules.in_right_order(&[1, 2]));
+        assert_eq!(rules.sort(&[2, 1]).ok(), Some(vec![1, 2]));
+
+        let err = rules.sort(&[3, 4, 2, 1]).unwrap_err();
+        assert_eq!(err.cycle, [1, 2, 3, 1]);
+        assert_eq!(
+            err.to_string(),
+            "rules form a cycle 1 -> 2 -> 3 -> 1 in update 3,4,2,1"
+        );
+        assert!(part2("1|2\n2|3\n3|1\n\n3,2,1").is_err());
+    }
+}
@@ -0,0 +1,580 @@
+use common::{bench, Runner};
+use rayon::prelude::*;
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 6)?;
+    if runner.flag("--bench") {
+        let lab: Lab = runner.input().parse()?;
+        bench("part2 sequential", 20, || count_loops(&lab));
+        bench("part2 parallel", 20, || count_loops_parallel(&lab));
+    }
+    if runner.flag("--watch") {
+        let lab: Lab = runner.input().parse()?;
+        let delay = runner.value("--delay").map(str::parse).transpose()?;
+        let mut player = Player::new(
+            delay.unwrap_or(100),
+            runner.value("--frames").map(PathBuf::from),
+        );
+        if runner.value("--part") == Some("2") {
+            watch_loops(&lab, &mut player)?;
+        } else {
+            watch_patrol(&lab, &mut player)?;
+        }
+    }
+    runner.part(1, part1)?;
+    if runner.flag("--sequential") {
+        runner.part(2, |input| {
+            Ok::<_, Box<dyn Error>>(count_loops(&input.parse()?))
+        })?;
+    } else {
+        runner.part(2, part2)?;
+    }
+    Ok(())
+}
+
+const DIRS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Marks a jump that walks off the edge of the lab
+const EXIT: usize = usize::MAX;
+
+fn next_dir_idx(dir: usize) -> usize {
+    (dir + 1) % DIRS.len()
+}
+
+struct Lab {
+    h: usize,
+    w: usize,
+    blocked: Vec<bool>,
+    start: usize,
+    /// `jumps[dir][cell]` is where the guard stops when walking from `cell` in direction `dir`:
+    /// the cell in front of the next obstacle, or `EXIT`
+    jumps: [Vec<usize>; 4],
+}
+
+impl FromStr for Lab {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s.lines().collect::<Vec<_>>();
+        let h = lines.len();
+        let w = lines.first().map_or(0, |line| line.len());
+        let mut blocked = Vec::with_capacity(h * w);
+        let mut start = None;
+        for line in lines {
+            if line.len() != w {
+                return Err("Ragged map");
+            }
+            for c in line.bytes() {
+                if c == b'^' {
+                    start = Some(blocked.len());
+                }
+                blocked.push(c == b'#');
+            }
+        }
+        let start = start.ok_or("No guard")?;
+        let mut lab = Self {
+            h,
+            w,
+            blocked,
+            start,
+            jumps: Default::default(),
+        };
+        lab.jumps = std::array::from_fn(|dir| lab.jump_table(dir));
+        Ok(lab)
+    }
+}
+
+impl Lab {
+    fn step(&self, cell: usize, dir: usize) -> Option<usize> {
+        let (dy, dx) = DIRS[dir];
+        let y = (cell / self.w)
+            .checked_add_signed(dy)
+            .filter(|&y| y < self.h)?;
+        let x = (cell % self.w)
+            .checked_add_signed(dx)
+            .filter(|&x| x < self.w)?;
+        Some(y * self.w + x)
+    }
+
+    /// Fill in the jumps for one direction, visiting each cell after the cell in front of it
+    fn jump_table(&self, dir: usize) -> Vec<usize> {
+        let n = self.h * self.w;
+        let mut jumps = vec![EXIT; n];
+        let forward = dir == 1 || dir == 2;
+        for i in 0..n {
+            let cell = if forward { n - 1 - i } else { i };
+            jumps[cell] = match self.step(cell, dir) {
+                None => EXIT,
+                Some(next) if self.blocked[next] => cell,
+                Some(next) => jumps[next],
+            };
+        }
+        jumps
+    }
+
+    fn distance(&self, from: usize, to: usize, dir: usize) -> Option<usize> {
+        let (fy, fx) = ((from / self.w) as isize, (from % self.w) as isize);
+        let (ty, tx) = ((to / self.w) as isize, (to % self.w) as isize);
+        let steps = match DIRS[dir] {
+            (0, dx) if fy == ty => (tx - fx) * dx,
+            (dy, 0) if fx == tx => (ty - fy) * dy,
+            _ => return None,
+        };
+        (steps > 0).then_some(steps as usize)
+    }
+
+    /// Where the guard stops walking from `cell` in direction `dir` once an extra obstacle is
+    /// placed. Only a jump passing over the obstacle changes, so the tables stay untouched.
+    fn jump(&self, cell: usize, dir: usize, obstacle: usize) -> usize {
+        let stop = self.jumps[dir][cell];
+        let Some(steps) = self.distance(cell, obstacle, dir) else {
+            return stop;
+        };
+        if stop != EXIT && self.distance(cell, stop, dir).unwrap_or(0) < steps {
+            return stop;
+        }
+        let (dy, dx) = DIRS[dir];
+        let offset = dy * self.w as isize + dx;
+        cell.wrapping_add_signed((steps as isize - 1) * offset)
+    }
+
+    fn patrol(&self, obstacle: Option<usize>) -> Patrol<'_> {
+        Patrol {
+            lab: self,
+            obstacle,
+            state: Some((self.start, 0)),
+        }
+    }
+
+    fn path(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.blocked.len()];
+        self.patrol(None)
+            .filter(|&(cell, _)| !std::mem::replace(&mut seen[cell], true))
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    /// Whether the guard gets stuck in a loop with an extra obstacle. Only the cells where the
+    /// guard turns are recorded, since a loop has to repeat one of them.
+    fn loops(&self, obstacle: usize, visited: &mut Visited) -> bool {
+        visited.clear();
+        let (mut cell, mut dir) = (self.start, 0);
+        loop {
+            let stop = self.jump(cell, dir, obstacle);
+            if stop == EXIT {
+                return false;
+            }
+            if !visited.insert(stop * DIRS.len() + dir) {
+                return true;
+            }
+            (cell, dir) = (stop, next_dir_idx(dir));
+        }
+    }
+}
+
+/// Each (cell, direction) state of the guard in turn, where a turn is a step that only
+/// changes direction
+struct Patrol<'a> {
+    lab: &'a Lab,
+    obstacle: Option<usize>,
+    state: Option<(usize, usize)>,
+}
+
+impl Iterator for Patrol<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (cell, dir) = self.state?;
+        self.state = match self.lab.step(cell, dir) {
+            None => None,
+            Some(next) if self.lab.blocked[next] || self.obstacle == Some(next) => {
+                Some((cell, next_dir_idx(dir)))
+            }
+            Some(next) => Some((next, dir)),
+        };
+        Some((cell, dir))
+    }
+}
+
+/// A bitset of (cell, direction) states which is cleared in time proportional to the
+/// number of states inserted since the last clear
+struct Visited {
+    words: Vec<u64>,
+    touched: Vec<usize>,
+}
+
+impl Visited {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            touched: vec![],
+        }
+    }
+
+    fn insert(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        if self.words[word] & bit != 0 {
+            return false;
+        }
+        if self.words[word] == 0 {
+            self.touched.push(word);
+        }
+        self.words[word] |= bit;
+        true
+    }
+
+    fn clear(&mut self) {
+        for word in self.touched.drain(..) {
+            self.words[word] = 0;
+        }
+    }
+}
+
+/// A new obstacle only matters on the guard's path, and not where the guard starts
+fn candidates(lab: &Lab) -> Vec<usize> {
+    lab.path().into_iter().skip(1).collect()
+}
+
+fn count_loops(lab: &Lab) -> usize {
+    let mut visited = Visited::new(lab.blocked.len() * DIRS.len());
+    candidates(lab)
+        .into_iter()
+        .filter(|&obstacle| lab.loops(obstacle, &mut visited))
+        .count()
+}
+
+fn count_loops_parallel(lab: &Lab) -> usize {
+    candidates(lab)
+        .into_par_iter()
+        .map_init(
+            || Visited::new(lab.blocked.len() * DIRS.len()),
+            |visited, obstacle| lab.loops(obstacle, visited),
+        )
+        .filter(|&looped| looped)
+        .count()
+}
+
+const GUARD: [char; 4] = ['^', '>', 'v', '<'];
+const VERTICAL: u8 = 1;
+const HORIZONTAL: u8 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Style {
+    Visited,
+    Path,
+}
+
+struct Trail {
+    marks: Vec<u8>,
+}
+
+impl Trail {
+    fn new(lab: &Lab) -> Self {
+        Self {
+            marks: vec![0; lab.blocked.len()],
+        }
+    }
+
+    fn mark(&mut self, cell: usize, dir: usize) {
+        self.marks[cell] |= if DIRS[dir].1 == 0 {
+            VERTICAL
+        } else {
+            HORIZONTAL
+        };
+    }
+}
+
+fn render(
+    lab: &Lab,
+    trail: &Trail,
+    guard: Option<(usize, usize)>,
+    obstacle: Option<usize>,
+    style: Style,
+) -> String {
+    let mut out = String::with_capacity(lab.blocked.len() + lab.h);
+    for cell in 0..lab.blocked.len() {
+        let c = match (guard, trail.marks[cell]) {
+            (Some((at, dir)), _) if at == cell => GUARD[dir],
+            _ if obstacle == Some(cell) => 'O',
+            _ if lab.blocked[cell] => '#',
+            (_, 0) => '.',
+            _ if style == Style::Visited => 'X',
+            (_, VERTICAL) => '|',
+            (_, HORIZONTAL) => '-',
+            _ => '+',
+        };
+        out.push(c);
+        if cell % lab.w == lab.w - 1 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Keys read from stdin while watching, each followed by enter
+enum Control {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+fn controls() -> Receiver<Control> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            for c in line.chars().chain(line.is_empty().then_some('s')) {
+                let control = match c {
+                    'p' | ' ' => Control::Pause,
+                    's' | 'n' => Control::Step,
+                    '+' | 'f' => Control::Faster,
+                    '-' | 'l' => Control::Slower,
+                    'q' => Control::Quit,
+                    _ => continue,
+                };
+                if tx.send(control).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    rx
+}
+
+struct Player {
+    delay: Duration,
+    paused: bool,
+    controls: Option<Receiver<Control>>,
+    frames: Option<PathBuf>,
+    count: usize,
+}
+
+impl Player {
+    fn new(delay_ms: u64, frames: Option<PathBuf>) -> Self {
+        Self {
+            delay: Duration::from_millis(delay_ms),
+            paused: false,
+            controls: None,
+            frames,
+            count: 0,
+        }
+    }
+
+    /// Show a frame and wait for the next one, returning false once the viewer quits
+    fn show(&mut self, frame: &str) -> io::Result<bool> {
+        self.count += 1;
+        if let Some(dir) = &self.frames {
+            fs::create_dir_all(dir)?;
+            fs::write(dir.join(format!("frame{:05}.txt", self.count)), frame)?;
+            return Ok(true);
+        }
+
+        let mut stderr = io::stderr().lock();
+        write!(stderr, "\x1b[H\x1b[2J{frame}")?;
+        writeln!(
+            stderr,
+            "{} every {}ms | p pause, s step, + faster, - slower, q quit (then enter)",
+            if self.paused { "paused" } else { "playing" },
+            self.delay.as_millis()
+        )?;
+        stderr.flush()?;
+
+        let controls = self.controls.get_or_insert_with(controls);
+        loop {
+            let control = if self.paused {
+                controls.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                controls.recv_timeout(self.delay)
+            };
+            match control {
+                Err(RecvTimeoutError::Timeout) => return Ok(true),
+                // Without stdin, keep playing
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.paused = false;
+                    thread::sleep(self.delay);
+                    return Ok(true);
+                }
+                Ok(Control::Pause) => {
+                    self.paused = !self.paused;
+                    if !self.paused {
+                        return Ok(true);
+                    }
+                }
+                Ok(Control::Step) if self.paused => return Ok(true),
+                Ok(Control::Step) => self.paused = true,
+                Ok(Control::Faster) => self.delay /= 2,
+                Ok(Control::Slower) => self.delay = (self.delay * 2).max(Duration::from_millis(1)),
+                Ok(Control::Quit) => return Ok(false),
+            }
+        }
+    }
+}
+
+fn watch_patrol(lab: &Lab, player: &mut Player) -> io::Result<()> {
+    let mut trail = Trail::new(lab);
+    for (step, (cell, dir)) in lab.patrol(None).enumerate() {
+        trail.mark(cell, dir);
+        let frame = render(lab, &trail, Some((cell, dir)), None, Style::Visited);
+        if !player.show(&format!("step {step}\n{frame}"))? {
+            return Ok(());
+        }
+    }
+    let frame = render(lab, &trail, None, None, Style::Visited);
+    player.show(&format!("left the lab\n{frame}"))?;
+    Ok(())
+}
+
+fn watch_loops(lab: &Lab, player: &mut Player) -> io::Result<()> {
+    let mut visited = Visited::new(lab.blocked.len() * DIRS.len());
+    let obstructions = candidates(lab)
+        .into_iter()
+        .filter(|&obstacle| lab.loops(obstacle, &mut visited))
+        .collect::<Vec<_>>();
+    for (i, &obstacle) in obstructions.iter().enumerate() {
+        let (y, x) = (obstacle / lab.w, obstacle % lab.w);
+        let mut trail = Trail::new(lab);
+        visited.clear();
+        for (step, (cell, dir)) in lab.patrol(Some(obstacle)).enumerate() {
+            trail.mark(cell, dir);
+            let looped = !visited.insert(cell * DIRS.len() + dir);
+            let frame = render(lab, &trail, Some((cell, dir)), Some(obstacle), Style::Path);
+            let header = format!(
+                "obstruction {}/{} at {y},{x}, step {step}{}",
+                i + 1,
+                obstructions.len(),
+                if looped { ", loop closed" } else { "" }
+            );
+            if !player.show(&format!("{header}\n{frame}"))? {
+                return Ok(());
+            }
+            if looped {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    let lab: Lab = input.parse()?;
+    Ok(lab.path().len())
+}
+
+fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    let lab: Lab = input.parse()?;
+    Ok(count_loops_parallel(&lab))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    const EXAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn part1_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part1(EXAMPLE)?, 41);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part2(EXAMPLE)?, 6);
+        let lab: Lab = EXAMPLE.parse()?;
+        assert_eq!(count_loops(&lab), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn render_like_puzzle() -> Result<(), Box<dyn Error>> {
+        let lab: Lab = EXAMPLE.parse()?;
+        let mut trail = Trail::new(&lab);
+        for (cell, dir) in lab.patrol(None) {
+            trail.mark(cell, dir);
+        }
+        let visited = "....#.....
+....XXXXX#
+....X...X.
+..#.X...X.
+..XXXXX#X.
+..X.X.X.X.
+.#XXXXXXX.
+.XXXXXXX#.
+#XXXXXXX..
+......#X..
+";
+        assert_eq!(render(&lab, &trail, None, None, Style::Visited), visited);
+
+        let obstacle = 6 * lab.w + 3;
+        let mut trail = Trail::new(&lab);
+        let mut seen = HashSet::new();
+        let mut guard = None;
+        for state in lab.patrol(Some(obstacle)) {
+            trail.mark(state.0, state.1);
+            guard = Some(state);
+            if !seen.insert(state) {
+                break;
+            }
+        }
+        let looped = "....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+";
+        assert_eq!(
+            render(&lab, &trail, guard, Some(obstacle), Style::Path),
+            looped
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn jumps_match_walking() -> Result<(), Box<dyn Error>> {
+        let lab: Lab = EXAMPLE.parse()?;
+        let n = lab.blocked.len();
+        for obstacle in (0..n).filter(|&o| !lab.blocked[o]) {
+            for cell in (0..n).filter(|&c| !lab.blocked[c] && c != obstacle) {
+                for dir in 0..DIRS.len() {
+                    let mut walked = cell;
+                    let expected = loop {
+                        match lab.step(walked, dir) {
+                            None => break EXIT,
+                            Some(next) if lab.blocked[next] || next == obstacle => break walked,
+                            Some(next) => walked = next,
+                        }
+                    };
+                    assert_eq!(lab.jump(cell, dir, obstacle), expected);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,506 @@
+use std::{error::Error, str::FromStr};
+
+use common::{OrOverflow, Runner};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 7)?;
+    let custom = runner.value("--ops").map(parse_ops).transpose()?;
+    let custom = custom
+        .iter()
+        .flatten()
+        .map(|op| op.as_ref())
+        .collect::<Vec<_>>();
+    let ops = if custom.is_empty() {
+        &PART2[..]
+    } else {
+        &custom
+    };
+    if let Some(mode) = runner.value("--witness") {
+        eprint!("{}", witness(runner.input(), ops, mode.parse()?)?);
+    }
+    runner.part(1, part1)?;
+    if custom.is_empty() {
+        runner.part(2, part2)?;
+    } else {
+        runner.part(2, |input| calibrate(input, &custom))?;
+    }
+    Ok(())
+}
+
+/// A binary operator in an equation, evaluated left to right. The solver works backwards
+/// from the test value, so each operator also needs an inverse.
+trait Operator {
+    fn symbol(&self) -> &'static str;
+
+    /// `a op b`, or `None` if it overflows or is undefined
+    fn apply(&self, a: usize, b: usize) -> Option<usize>;
+
+    /// The left operands `a` for which `a op b == c`
+    fn unapply(&self, c: usize, b: usize) -> Inverse;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Inverse {
+    None,
+    One(usize),
+    /// Every left operand gives the same result, as in `a * 0`
+    Any,
+}
+
+impl From<Option<usize>> for Inverse {
+    fn from(a: Option<usize>) -> Self {
+        a.map_or(Inverse::None, Inverse::One)
+    }
+}
+
+struct Add;
+
+impl Operator for Add {
+    fn symbol(&self) -> &'static str {
+        "+"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        a.checked_add(b)
+    }
+
+    fn unapply(&self, c: usize, b: usize) -> Inverse {
+        c.checked_sub(b).into()
+    }
+}
+
+struct Mul;
+
+impl Operator for Mul {
+    fn symbol(&self) -> &'static str {
+        "*"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        a.checked_mul(b)
+    }
+
+    fn unapply(&self, c: usize, b: usize) -> Inverse {
+        match (c, b) {
+            (0, 0) => Inverse::Any,
+            (_, 0) => Inverse::None,
+            _ => c.is_multiple_of(b).then(|| c / b).into(),
+        }
+    }
+}
+
+struct Concat {
+    base: usize,
+}
+
+impl Concat {
+    /// `base` raised to the number of digits in `b`
+    fn shift(&self, b: usize) -> Option<usize> {
+        let mut shift = self.base;
+        let mut rest = b / self.base;
+        while rest > 0 {
+            shift = shift.checked_mul(self.base)?;
+            rest /= self.base;
+        }
+        Some(shift)
+    }
+}
+
+impl Operator for Concat {
+    fn symbol(&self) -> &'static str {
+        "||"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        a.checked_mul(self.shift(b)?)?.checked_add(b)
+    }
+
+    fn unapply(&self, c: usize, b: usize) -> Inverse {
+        // An operand too wide to shift by can't be a suffix of c either
+        let Some(shift) = self.shift(b) else {
+            return Inverse::None;
+        };
+        (c % shift == b).then(|| c / shift).into()
+    }
+}
+
+struct Sub;
+
+impl Operator for Sub {
+    fn symbol(&self) -> &'static str {
+        "-"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        a.checked_sub(b)
+    }
+
+    fn unapply(&self, c: usize, b: usize) -> Inverse {
+        c.checked_add(b).into()
+    }
+}
+
+struct Xor;
+
+impl Operator for Xor {
+    fn symbol(&self) -> &'static str {
+        "^"
+    }
+
+    fn apply(&self, a: usize, b: usize) -> Option<usize> {
+        Some(a ^ b)
+    }
+
+    fn unapply(&self, c: usize, b: usize) -> Inverse {
+        Inverse::One(c ^ b)
+    }
+}
+
+const PART1: [&dyn Operator; 2] = [&Add, &Mul];
+const PART2: [&dyn Operator; 3] = [&Add, &Mul, &Concat { base: 10 }];
+
+fn parse_ops(s: &str) -> Result<Vec<Box<dyn Operator>>, Box<dyn Error>> {
+    s.split(',')
+        .map(|op| -> Result<Box<dyn Operator>, Box<dyn Error>> {
+            Ok(match op.trim() {
+                "+" => Box::new(Add),
+                "*" => Box::new(Mul),
+                "-" => Box::new(Sub),
+                "^" => Box::new(Xor),
+                "||" => Box::new(Concat { base: 10 }),
+                op => match op.strip_prefix("||").map(str::parse) {
+                    Some(Ok(base @ 2..)) => Box::new(Concat { base }),
+                    _ => return Err(format!("unknown operator {op:?}").into()),
+                },
+            })
+        })
+        .collect()
+}
+
+fn parse(line: &str) -> Result<(usize, Vec<usize>), Box<dyn Error>> {
+    let nums = line
+        .split(&[':', ' '])
+        .filter(|s| !s.is_empty())
+        .map(|n| n.parse())
+        .collect::<Result<Vec<usize>, _>>()?;
+    match nums.split_first() {
+        Some((&test, nums)) if !nums.is_empty() => Ok((test, nums.to_vec())),
+        _ => Err(format!("no numbers in equation {line:?}").into()),
+    }
+}
+
+/// Whether some choice of operators between the numbers produces the test value. Searches
+/// backwards from the test value, undoing the last operation first, which prunes every
+/// operator that can't have produced the value reached so far.
+fn solvable(test: usize, nums: &[usize], ops: &[&dyn Operator]) -> bool {
+    let mut frontier = vec![(test, nums.len() - 1)];
+    while let Some((test, i)) = frontier.pop() {
+        let n = nums[i];
+        if i == 0 {
+            if test == n {
+                return true;
+            }
+            continue;
+        }
+        for op in ops {
+            match op.unapply(test, n) {
+                Inverse::None => (),
+                Inverse::One(test) => frontier.push((test, i - 1)),
+                // Any value of the numbers before will do, as long as one can be computed
+                Inverse::Any => {
+                    if brute_force(&nums[..i], ops).next().is_some() {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Every choice of operators, as indices into `ops`, that produces the test value. Runs the
+/// same backward search as `solvable`, stopping after `limit` choices.
+fn witnesses(test: usize, nums: &[usize], ops: &[&dyn Operator], limit: usize) -> Vec<Vec<usize>> {
+    fn search(
+        test: usize,
+        nums: &[usize],
+        ops: &[&dyn Operator],
+        chosen: &mut Vec<usize>,
+        found: &mut Vec<Vec<usize>>,
+        limit: usize,
+    ) {
+        let Some((&n, rest)) = nums.split_last() else {
+            return;
+        };
+        if rest.is_empty() {
+            if test == n && found.len() < limit {
+                found.push(chosen.iter().rev().copied().collect());
+            }
+            return;
+        }
+        for (i, op) in ops.iter().enumerate() {
+            match op.unapply(test, n) {
+                Inverse::None => (),
+                Inverse::One(test) => {
+                    chosen.push(i);
+                    search(test, rest, ops, chosen, found, limit);
+                    chosen.pop();
+                }
+                Inverse::Any => {
+                    for (_, before) in brute_force(rest, ops) {
+                        if found.len() == limit {
+                            return;
+                        }
+                        let after = chosen.iter().rev().copied();
+                        found.push(before.into_iter().chain([i]).chain(after).collect());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut found = vec![];
+    search(test, nums, ops, &mut vec![], &mut found, limit);
+    found
+}
+
+/// Evaluate every choice of operators left to right, skipping those that overflow
+fn brute_force<'a>(
+    nums: &'a [usize],
+    ops: &'a [&dyn Operator],
+) -> impl Iterator<Item = (usize, Vec<usize>)> + 'a {
+    let slots = nums.len() - 1;
+    let count = ops.len().checked_pow(slots as u32).unwrap_or(usize::MAX);
+    (0..count).filter_map(move |mut k| {
+        let mut choice = Vec::with_capacity(slots);
+        let mut value = nums[0];
+        for &n in &nums[1..] {
+            let i = k % ops.len();
+            k /= ops.len();
+            value = ops[i].apply(value, n)?;
+            choice.push(i);
+        }
+        Some((value, choice))
+    })
+}
+
+fn expression(nums: &[usize], ops: &[&dyn Operator], choice: &[usize]) -> String {
+    let mut out = nums[0].to_string();
+    for (&n, &i) in nums[1..].iter().zip(choice) {
+        out += &format!(" {} {n}", ops[i].symbol());
+    }
+    out
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Witness {
+    One,
+    All,
+    Count,
+}
+
+impl FromStr for Witness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "one" => Ok(Self::One),
+            "all" => Ok(Self::All),
+            "count" => Ok(Self::Count),
+            _ => Err(format!(
+                "unknown witness mode {s:?}, expected one, all or count"
+            )),
+        }
+    }
+}
+
+fn witness(input: &str, ops: &[&dyn Operator], mode: Witness) -> Result<String, Box<dyn Error>> {
+    let mut out = String::new();
+    for line in input.lines() {
+        let (test, nums) = parse(line)?;
+        let limit = if mode == Witness::One { 1 } else { usize::MAX };
+        let found = witnesses(test, &nums, ops, limit);
+        if found.is_empty() {
+            let closest = brute_force(&nums, ops).min_by_key(|(value, _)| value.abs_diff(test));
+            out += &match closest {
+                Some((value, choice)) => format!(
+                    "{test}: unsolvable, closest is {value} = {} (off by {})\n",
+                    expression(&nums, ops, &choice),
+                    value.abs_diff(test)
+                ),
+                None => format!("{test}: unsolvable, every choice overflows\n"),
+            };
+        } else if mode == Witness::Count {
+            let plural = if found.len() == 1 { "" } else { "s" };
+            out += &format!("{test}: {} solution{plural}\n", found.len());
+        } else {
+            for choice in found {
+                out += &format!("{test} = {}\n", expression(&nums, ops, &choice));
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn calibrate(input: &str, ops: &[&dyn Operator]) -> Result<usize, Box<dyn Error>> {
+    let mut sum = 0;
+    for line in input.lines() {
+        let (test, nums) = parse(line)?;
+        if solvable(test, &nums, ops) {
+            sum = test.checked_add(sum).or_overflow("summing test values")?;
+        }
+    }
+    Ok(sum)
+}
+
+fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    calibrate(input, &PART1)
+}
+
+fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    calibrate(input, &PART2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Rng;
+
+    const EXAMPLE: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    #[test]
+    fn part1_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part1(EXAMPLE)?, 3749);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(part2(EXAMPLE)?, 11387);
+        Ok(())
+    }
+
+    #[test]
+    fn operators_invert() -> Result<(), Box<dyn Error>> {
+        let ops = parse_ops("+,*,-,^,||,||2,||16")?;
+        for op in &ops {
+            for a in [0, 1, 7, 10, 99, 1234] {
+                for b in [0, 1, 3, 10, 255] {
+                    if let Some(c) = op.apply(a, b) {
+                        let expected = if op.symbol() == "*" && b == 0 {
+                            Inverse::Any
+                        } else {
+                            Inverse::One(a)
+                        };
+                        assert_eq!(op.unapply(c, b), expected, "{a} {} {b}", op.symbol());
+                    }
+                }
+            }
+        }
+        assert_eq!(Concat { base: 10 }.apply(12, 0), Some(120));
+        assert_eq!(Concat { base: 2 }.apply(0b101, 0b11), Some(0b10111));
+        assert!(parse_ops("+,%").is_err());
+
+        // Multiplying by zero solves a zero test value whatever comes before it
+        assert!(solvable(0, &[5, 0], &PART1));
+        assert!(!solvable(1, &[5, 0], &PART1));
+        assert_eq!(calibrate("12: 5 0 12\n0: 3 4 0", &PART1)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn witness_modes() -> Result<(), Box<dyn Error>> {
+        let all = witness("3267: 81 40 27", &PART1, Witness::All)?;
+        assert_eq!(all, "3267 = 81 * 40 + 27\n3267 = 81 + 40 * 27\n");
+        let one = witness("3267: 81 40 27", &PART1, Witness::One)?;
+        assert!(all.starts_with(&one));
+        assert_eq!(
+            witness("156: 15 6\n3267: 81 40 27", &PART2, Witness::Count)?,
+            "156: 1 solution\n3267: 2 solutions\n"
+        );
+        assert_eq!(witness("0: 5 0", &PART1, Witness::All)?, "0 = 5 * 0\n");
+        assert_eq!(
+            witness("83: 17 5", &PART2, Witness::One)?,
+            "83: unsolvable, closest is 85 = 17 * 5 (off by 2)\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn backward_search_matches_brute_force() -> Result<(), Box<dyn Error>> {
+        fn check(test: usize, nums: &[usize], ops: &[&dyn Operator]) {
+            let found = witnesses(test, nums, ops, usize::MAX);
+            let mut brute = brute_force(nums, ops)
+                .filter(|&(value, _)| value == test)
+                .map(|(_, choice)| choice)
+                .collect::<Vec<_>>();
+            brute.sort();
+            let mut sorted = found.clone();
+            sorted.sort();
+            assert_eq!(sorted, brute, "{test}: {nums:?}");
+            assert_eq!(
+                solvable(test, nums, ops),
+                !found.is_empty(),
+                "{test}: {nums:?}"
+            );
+        }
+
+        // A zero operand lets every choice before it through, even ones that underflow
+        let with_sub = parse_ops("+,*,-")?;
+        let with_sub = with_sub.iter().map(|op| op.as_ref()).collect::<Vec<_>>();
+        for (test, nums) in [
+            (0, &[5, 0][..]),
+            (12, &[5, 0, 12]),
+            (0, &[0, 0, 0]),
+            (0, &[3, 0, 0]),
+            (7, &[0, 7]),
+            (0, &[2, 9, 0]),
+            (4, &[2, 9, 0, 4]),
+        ] {
+            check(test, nums, &PART1);
+            check(test, nums, &PART2);
+            check(test, nums, &with_sub);
+        }
+
+        let mut rng = Rng::new(7);
+        for _ in 0..500 {
+            let nums = (0..2 + rng.below(4))
+                .map(|_| rng.below(20))
+                .collect::<Vec<_>>();
+            check(rng.below(2000), &nums, &PART2);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sum_near_limit() -> Result<(), Box<dyn Error>> {
+        let max = usize::MAX;
+        assert_eq!(calibrate(&format!("{max}: {max} 1"), &PART2)?, max);
+        let half = usize::MAX / 2 + 1;
+        let err = calibrate(&format!("{half}: {half} 1\n{half}: 1 {half}"), &PART1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "integer overflow while summing test values"
+        );
+        // Concatenating past the limit is a dead end rather than a wrapped value
+        assert_eq!(calibrate(&format!("{max}: 2 {max}"), &PART2)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn custom_operators() -> Result<(), Box<dyn Error>> {
+        let ops = parse_ops("-,^")?;
+        let ops = ops.iter().map(|op| op.as_ref()).collect::<Vec<_>>();
+        // 10 - 3 ^ 5 = 2, 20 ^ 4 - 1 = 15
+        assert_eq!(calibrate("2: 10 3 5\n15: 20 4 1\n9: 1 1", &ops)?, 17);
+        Ok(())
+    }
+}
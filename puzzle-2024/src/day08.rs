@@ -0,0 +1,443 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    error::Error,
+    str::FromStr,
+};
+
+use common::Runner;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 8)?;
+    let (rule1, rule2) = rules_from_args(&runner)?;
+    if runner.flag("--stats") {
+        let map = parse(runner.input());
+        eprint!("part 1, {rule1:?}\n{}", stats(&map, rule1));
+        eprint!("part 2, {rule2:?}\n{}", stats(&map, rule2));
+    }
+    if let Some(style) = runner.value("--render") {
+        let rule = if runner.value("--part") == Some("2") {
+            rule2
+        } else {
+            rule1
+        };
+        let only = runner
+            .value("--frequency")
+            .map(|f| match f.as_bytes() {
+                [f] => Ok(*f),
+                _ => Err(format!("expected a single character frequency, got {f:?}")),
+            })
+            .transpose()?;
+        eprint!(
+            "{}",
+            render(&parse(runner.input()), rule, only, style.parse()?)
+        );
+    }
+    if rule1 == Rule::PART1 {
+        runner.part(1, part1)?;
+    } else {
+        runner.part(1, |input| count_antinodes(&parse(input), rule1))?;
+    }
+    if rule2 == Rule::PART2 {
+        runner.part(2, part2)?;
+    } else {
+        runner.part(2, |input| count_antinodes(&parse(input), rule2))?;
+    }
+    Ok(())
+}
+
+fn rules_from_args(runner: &Runner) -> Result<(Rule, Rule), Box<dyn Error>> {
+    let mut rule1 = Rule::PART1;
+    if let Some(n) = runner.value("--ratio") {
+        rule1 = Rule::Ratio {
+            ratio: n.parse()?,
+            inner: runner.flag("--inner"),
+        };
+    } else if runner.flag("--inner") {
+        rule1 = Rule::Ratio {
+            ratio: 2,
+            inner: true,
+        };
+    }
+    let mut rule2 = Rule::PART2;
+    if let Some(n) = runner.value("--limit") {
+        rule2 = Rule::Resonance {
+            limit: Some(n.parse()?),
+        };
+    }
+    Ok((rule1, rule2))
+}
+
+type Antennas = BTreeMap<u8, Vec<(i32, i32)>>;
+
+struct Map {
+    height: i32,
+    width: i32,
+    antennas: Antennas,
+}
+
+impl Map {
+    fn contains(&self, (y, x): (i32, i32)) -> bool {
+        y >= 0 && y < self.height && x >= 0 && x < self.width
+    }
+}
+
+fn parse(input: &str) -> Map {
+    let grid: Vec<_> = input.lines().map(|line| line.as_bytes()).collect();
+    let height = grid.len() as i32;
+    let width = grid[0].len() as i32;
+    let mut antennas = BTreeMap::new();
+    for y in 0..height {
+        for x in 0..width {
+            let a = grid[y as usize][x as usize];
+            if a != b'.' {
+                antennas.entry(a).or_insert(vec![]).push((y, x));
+            }
+        }
+    }
+    Map {
+        height,
+        width,
+        antennas,
+    }
+}
+
+/// Where a pair of antennas with the same frequency creates antinodes. Antinodes always
+/// sit on grid points in line with the pair, which are `a + k * step` where `step` is the
+/// delta between the antennas divided by its gcd, and `b = a + g * step`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Rule {
+    /// Points where one antenna is exactly `ratio` times as far away as the other. These
+    /// are outside the pair, or also between the antennas when `inner` is set.
+    Ratio { ratio: u32, inner: bool },
+    /// Every point in line with the pair, reaching at most `limit` antenna spacings past
+    /// either antenna, or all the way to the edge of the map
+    Resonance { limit: Option<u32> },
+}
+
+impl Rule {
+    const PART1: Self = Self::Ratio {
+        ratio: 2,
+        inner: false,
+    };
+    const PART2: Self = Self::Resonance { limit: None };
+
+    fn antinodes(&self, map: &Map, a: (i32, i32), b: (i32, i32)) -> Vec<(i32, i32)> {
+        let (dy, dx) = (b.0 - a.0, b.1 - a.1);
+        let g = gcd(dy.unsigned_abs(), dx.unsigned_abs()) as i32;
+        let (sy, sx) = (dy / g, dx / g);
+        let at = |k: i32| (a.0 + k * sy, a.1 + k * sx);
+
+        match *self {
+            Self::Ratio { ratio, inner } => {
+                let r = ratio as i32;
+                // Solve |k| = r * |k - g| and r * |k| = |k - g| on each side of the pair
+                let mut ks = vec![];
+                if r > 1 && g % (r - 1) == 0 {
+                    ks.extend([r * g / (r - 1), -g / (r - 1)]);
+                }
+                if inner && g % (r + 1) == 0 {
+                    ks.extend([r * g / (r + 1), g / (r + 1)]);
+                }
+                ks.dedup();
+                ks.into_iter()
+                    .map(at)
+                    .filter(|&p| map.contains(p))
+                    .collect()
+            }
+            Self::Resonance { limit } => {
+                let reach = limit.map_or(i32::MAX, |limit| {
+                    g.saturating_mul(i32::try_from(limit).unwrap_or(i32::MAX))
+                });
+                let on_map = |&p: &(i32, i32)| map.contains(p);
+                let forward = (0..=g.saturating_add(reach)).map(at).take_while(on_map);
+                let backward = (1..=reach).map(|k| at(-k)).take_while(on_map);
+                forward.chain(backward).collect()
+            }
+        }
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn antinodes(map: &Map, rule: Rule) -> BTreeMap<u8, HashSet<(i32, i32)>> {
+    map.antennas
+        .iter()
+        .map(|(&frequency, coords)| {
+            let mut antinodes = HashSet::new();
+            for (i, &a) in coords.iter().enumerate() {
+                for &b in &coords[i + 1..] {
+                    antinodes.extend(rule.antinodes(map, a, b));
+                }
+            }
+            (frequency, antinodes)
+        })
+        .collect()
+}
+
+fn count_antinodes(map: &Map, rule: Rule) -> usize {
+    antinodes(map, rule)
+        .into_values()
+        .flatten()
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// A table of the antennas, pairs and antinodes of each frequency. Antinodes that no
+/// other frequency creates are counted as unique.
+fn stats(map: &Map, rule: Rule) -> String {
+    let by_frequency = antinodes(map, rule);
+    let mut out = "frequency\tantennas\tpairs\tantinodes\tunique\n".to_string();
+    for (frequency, points) in &by_frequency {
+        let antennas = map.antennas[frequency].len();
+        let unique = points
+            .iter()
+            .filter(|p| {
+                by_frequency
+                    .iter()
+                    .all(|(f, others)| f == frequency || !others.contains(p))
+            })
+            .count();
+        out += &format!(
+            "{}\t{antennas}\t{}\t{}\t{unique}\n",
+            *frequency as char,
+            antennas * antennas.saturating_sub(1) / 2,
+            points.len()
+        );
+    }
+    out
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Style {
+    Plain,
+    Ansi,
+    Svg,
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "ansi" => Ok(Self::Ansi),
+            "svg" => Ok(Self::Svg),
+            _ => Err(format!(
+                "unknown render style {s:?}, expected plain, ansi or svg"
+            )),
+        }
+    }
+}
+
+const COLOURS: [u8; 12] = [31, 32, 33, 34, 35, 36, 91, 92, 93, 94, 95, 96];
+
+const CELL: i32 = 16;
+
+/// Draw the antennas and antinodes of every frequency, or only of the `only` frequency.
+/// Each frequency is a layer with its own colour, and an antinode shared by several
+/// frequencies takes the colour of the first.
+fn render(map: &Map, rule: Rule, only: Option<u8>, style: Style) -> String {
+    let layers = antinodes(map, rule)
+        .into_iter()
+        .filter(|(frequency, _)| only.is_none_or(|only| only == *frequency))
+        .collect::<Vec<_>>();
+    let antenna_at = |p: (i32, i32)| {
+        layers
+            .iter()
+            .enumerate()
+            .find(|(_, (frequency, _))| map.antennas[frequency].contains(&p))
+            .map(|(i, (frequency, _))| (i, *frequency))
+    };
+    let antinode_at = |p| layers.iter().position(|(_, points)| points.contains(&p));
+
+    if style == Style::Svg {
+        let (width, height) = (map.width * CELL, map.height * CELL);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"{}\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"#0f0f23\"/>\n",
+            CELL * 3 / 4
+        );
+        let hue = |i: usize| i * 360 / layers.len().max(1);
+        for y in 0..map.height {
+            for x in 0..map.width {
+                let (cx, cy) = (x * CELL + CELL / 2, y * CELL + CELL / 2);
+                if let Some(i) = antinode_at((y, x)) {
+                    out += &format!(
+                        "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{}\" fill=\"hsl({}, 70%, 50%)\" \
+                         fill-opacity=\"0.6\"/>\n",
+                        CELL * 2 / 5,
+                        hue(i)
+                    );
+                }
+                if let Some((i, frequency)) = antenna_at((y, x)) {
+                    let label = match frequency {
+                        b'<' => "&lt;".to_string(),
+                        b'>' => "&gt;".to_string(),
+                        b'&' => "&amp;".to_string(),
+                        _ => (frequency as char).to_string(),
+                    };
+                    out += &format!(
+                        "<text x=\"{cx}\" y=\"{cy}\" fill=\"hsl({}, 70%, 80%)\">{label}</text>\n",
+                        hue(i)
+                    );
+                }
+            }
+        }
+        out += "</svg>\n";
+        return out;
+    }
+
+    let mut out = String::new();
+    for y in 0..map.height {
+        for x in 0..map.width {
+            let (layer, c, bold) = match (antenna_at((y, x)), antinode_at((y, x))) {
+                (Some((i, frequency)), _) => (Some(i), frequency as char, false),
+                (None, Some(i)) => (Some(i), '#', true),
+                (None, None) => (None, '.', false),
+            };
+            match (style, layer) {
+                (Style::Ansi, Some(i)) => {
+                    let weight = if bold { "1;" } else { "" };
+                    out += &format!("\x1b[{weight}{}m{c}\x1b[0m", COLOURS[i % COLOURS.len()]);
+                }
+                _ => out.push(c),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn part1(input: &str) -> usize {
+    count_antinodes(&parse(input), Rule::PART1)
+}
+
+fn part2(input: &str) -> usize {
+    count_antinodes(&parse(input), Rule::PART2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), 14);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), 34);
+    }
+
+    #[test]
+    fn resonance_steps_by_gcd() {
+        let map = parse("a....\n.....\n..a..\n.....\n.....");
+        // Every point on the diagonal, not just every other one
+        assert_eq!(count_antinodes(&map, Rule::PART2), 5);
+        assert_eq!(count_antinodes(&map, Rule::PART1), 1);
+        let limited = Rule::Resonance { limit: Some(0) };
+        assert_eq!(count_antinodes(&map, limited), 3);
+
+        let map = parse("a..a.....");
+        let inner = Rule::Ratio {
+            ratio: 2,
+            inner: true,
+        };
+        assert_eq!(
+            inner.antinodes(&map, (0, 0), (0, 3)),
+            [(0, 6), (0, 2), (0, 1)]
+        );
+        let ratio4 = Rule::Ratio {
+            ratio: 4,
+            inner: false,
+        };
+        assert_eq!(ratio4.antinodes(&map, (0, 0), (0, 3)), [(0, 4)]);
+    }
+
+    #[test]
+    fn render_like_puzzle() {
+        let map = parse(EXAMPLE);
+        let part1 = "......#....#
+...#....0...
+....#0....#.
+..#....0....
+....0....#..
+.#....A.....
+...#........
+#......#....
+........A...
+.........A..
+..........#.
+..........#.
+";
+        assert_eq!(render(&map, Rule::PART1, None, Style::Plain), part1);
+        let part2 = "##....#....#
+.#.#....0...
+..#.#0....#.
+..##...0....
+....0....#..
+.#...#A....#
+...#..#.....
+#....#.#....
+..#.....A...
+....#....A..
+.#........#.
+...#......##
+";
+        assert_eq!(render(&map, Rule::PART2, None, Style::Plain), part2);
+    }
+
+    #[test]
+    fn render_layers() {
+        let map = parse(EXAMPLE);
+        let only_a = render(&map, Rule::PART1, Some(b'A'), Style::Plain);
+        assert_eq!(only_a.matches('#').count(), 5);
+        assert_eq!(only_a.matches('A').count(), 3);
+        assert!(!only_a.contains('0'));
+
+        let ansi = render(&map, Rule::PART1, None, Style::Ansi);
+        assert!(ansi.contains("\x1b[31m0\x1b[0m"));
+        assert!(ansi.contains("\x1b[1;32m#\x1b[0m"));
+
+        let svg = render(&map, Rule::PART1, Some(b'A'), Style::Svg);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<circle ").count(), 5);
+        assert_eq!(svg.matches("<text ").count(), 3);
+
+        let svg = render(&parse("<.\n.&"), Rule::PART1, None, Style::Svg);
+        assert!(svg.contains(">&lt;</text>"));
+        assert!(svg.contains(">&amp;</text>"));
+    }
+
+    #[test]
+    fn frequency_stats() {
+        let table = stats(&parse(EXAMPLE), Rule::PART1);
+        assert_eq!(
+            table,
+            "frequency\tantennas\tpairs\tantinodes\tunique\n0\t4\t6\t10\t9\nA\t3\t3\t5\t4\n"
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use std::{cmp::Reverse, collections::BinaryHeap, error::Error, fmt::Display, str::FromStr};
+
+use common::{bench, Runner};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 9)?;
+    if runner.flag("--bench") {
+        let input = runner.input();
+        bench("part2 heaps", 20, || part2(input));
+        bench("part2 naive", 20, || part2_naive(input));
+    }
+    let strategy = runner.value("--strategy").map(str::parse).transpose()?;
+    if strategy.is_some() || runner.flag("--layout") {
+        let mut disk = DiskMap::parse(runner.input());
+        let layout = runner.flag("--layout");
+        if layout {
+            eprintln!("{}", disk.layout());
+        }
+        disk.compact(strategy.unwrap_or(Strategy::Blocks), |disk, _| {
+            if layout {
+                eprintln!("{}", disk.layout());
+            }
+        });
+        eprintln!("checksum {}\n{}", disk.checksum(), disk.fragmentation());
+    }
+    runner.part(1, part1)?;
+    runner.part(2, part2)?;
+    Ok(())
+}
+
+const OFFSET: u8 = 48;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Strategy {
+    Blocks,
+    Files(Fit),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Fit {
+    First,
+    /// The smallest span that fits, leftmost among equals
+    Best,
+    /// The leftmost span the file fills exactly, so no sliver of free space is left
+    /// behind, falling back to the leftmost span that fits
+    MinFragments,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" => Ok(Self::Blocks),
+            "first-fit" => Ok(Self::Files(Fit::First)),
+            "best-fit" => Ok(Self::Files(Fit::Best)),
+            "min-fragments" => Ok(Self::Files(Fit::MinFragments)),
+            _ => Err(format!(
+                "unknown strategy {s:?}, expected blocks, first-fit, best-fit or min-fragments"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Move {
+    id: usize,
+    from: usize,
+    to: usize,
+    len: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct Fragmentation {
+    free_spans: usize,
+    free_blocks: usize,
+    largest_free_span: usize,
+    /// Free blocks before the last file block, which compaction tries to get rid of
+    gap_blocks: usize,
+    files: usize,
+    /// Contiguous runs of file blocks, equal to `files` when no file is split
+    file_fragments: usize,
+}
+
+impl Display for Fragmentation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} free blocks in {} spans (largest {}), {} before the last file block, \
+             {} files in {} fragments",
+            self.free_blocks,
+            self.free_spans,
+            self.largest_free_span,
+            self.gap_blocks,
+            self.files,
+            self.file_fragments
+        )
+    }
+}
+
+struct DiskMap {
+    blocks: Vec<Option<usize>>,
+    files: Vec<(usize, usize)>,
+}
+
+impl DiskMap {
+    fn parse(input: &str) -> Self {
+        let mut blocks = vec![];
+        let mut files = vec![];
+        for (i, &b) in input.trim_ascii_end().as_bytes().iter().enumerate() {
+            let len = (b - OFFSET) as usize;
+            if i % 2 == 0 {
+                files.push((blocks.len(), len));
+                blocks.extend(std::iter::repeat_n(Some(i / 2), len));
+            } else {
+                blocks.extend(std::iter::repeat_n(None, len));
+            }
+        }
+        Self { blocks, files }
+    }
+
+    /// The blocks as the puzzle draws them, such as `0..111`. With more than ten files every
+    /// block is padded to the width of the largest ID and separated by spaces.
+    fn layout(&self) -> String {
+        let width = self.files.len().saturating_sub(1).to_string().len();
+        let cells = self.blocks.iter().map(|block| match block {
+            Some(id) => format!("{id:0width$}"),
+            None => ".".repeat(width),
+        });
+        if width == 1 {
+            cells.collect()
+        } else {
+            cells.collect::<Vec<_>>().join(" ")
+        }
+    }
+
+    fn checksum(&self) -> usize {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, id)| id.map(|id| pos * id))
+            .sum()
+    }
+
+    fn free_spans(&self) -> Vec<(usize, usize)> {
+        let mut spans: Vec<(usize, usize)> = vec![];
+        for (pos, block) in self.blocks.iter().enumerate() {
+            match (block, spans.last_mut()) {
+                (Some(_), _) => (),
+                (None, Some((start, len))) if *start + *len == pos => *len += 1,
+                (None, _) => spans.push((pos, 1)),
+            }
+        }
+        spans
+    }
+
+    fn fragmentation(&self) -> Fragmentation {
+        let spans = self.free_spans();
+        let end = self.blocks.iter().rposition(Option::is_some).unwrap_or(0);
+        let file_fragments = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|&(pos, block)| block.is_some() && (pos == 0 || self.blocks[pos - 1] != *block))
+            .count();
+        Fragmentation {
+            free_spans: spans.len(),
+            free_blocks: spans.iter().map(|(_, len)| len).sum(),
+            largest_free_span: spans.iter().map(|&(_, len)| len).max().unwrap_or(0),
+            gap_blocks: self.blocks[..end].iter().filter(|b| b.is_none()).count(),
+            files: self.files.len(),
+            file_fragments,
+        }
+    }
+
+    fn compact(&mut self, strategy: Strategy, on_move: impl FnMut(&Self, &Move)) {
+        match strategy {
+            Strategy::Blocks => self.compact_blocks(on_move),
+            Strategy::Files(fit) => self.compact_files(fit, on_move),
+        }
+    }
+
+    fn compact_blocks(&mut self, mut on_move: impl FnMut(&Self, &Move)) {
+        let mut head = 0;
+        let mut tail = self.blocks.len();
+        loop {
+            while head < tail && self.blocks[head].is_some() {
+                head += 1;
+            }
+            while tail > head && self.blocks[tail - 1].is_none() {
+                tail -= 1;
+            }
+            if tail - head < 2 {
+                return;
+            }
+            tail -= 1;
+            let id = self.blocks[tail].take().unwrap_or_default();
+            self.blocks[head] = Some(id);
+            let step = Move {
+                id,
+                from: tail,
+                to: head,
+                len: 1,
+            };
+            on_move(self, &step);
+        }
+    }
+
+    fn compact_files(&mut self, fit: Fit, mut on_move: impl FnMut(&Self, &Move)) {
+        // Space freed by a file is never left of a file that has yet to move, so the spans
+        // only ever shrink
+        let mut spans = self.free_spans();
+        for id in (0..self.files.len()).rev() {
+            let (from, len) = self.files[id];
+            let fits = spans
+                .iter()
+                .enumerate()
+                .take_while(|(_, &(pos, _))| pos < from)
+                .filter(|(_, &(_, span))| span >= len);
+            let chosen = match fit {
+                Fit::First => fits.map(|(i, _)| i).next(),
+                Fit::Best => fits
+                    .min_by_key(|&(i, &(_, span))| (span, i))
+                    .map(|(i, _)| i),
+                Fit::MinFragments => {
+                    let fits = fits.collect::<Vec<_>>();
+                    fits.iter()
+                        .find(|(_, &(_, span))| span == len)
+                        .or(fits.first())
+                        .map(|&(i, _)| i)
+                }
+            };
+            let Some(i) = chosen else {
+                continue;
+            };
+            let to = spans[i].0;
+            spans[i] = (to + len, spans[i].1 - len);
+            self.blocks[from..from + len].fill(None);
+            self.blocks[to..to + len].fill(Some(id));
+            self.files[id] = (to, len);
+            on_move(self, &Move { id, from, to, len });
+        }
+    }
+}
+
+fn part1(input: &str) -> usize {
+    let bytes = input.trim_ascii_end().as_bytes();
+    let mut head = 0;
+    let mut tail = bytes.len() - 1;
+    let mut count = 0;
+    let mut sum = 0;
+    let mut partial_len = 0;
+    let mut partial_id = 0;
+    while head < tail {
+        let len = bytes[head] - OFFSET;
+        let id = head / 2;
+        for _ in 0..len {
+            sum += count * id;
+            count += 1;
+        }
+        head += 1;
+        let mut empty_len = bytes[head] - OFFSET;
+        while empty_len > 0 {
+            if partial_len == 0 {
+                partial_len = bytes[tail] - OFFSET;
+                partial_id = tail / 2;
+            }
+            let len = empty_len.min(partial_len);
+            for _ in 0..len {
+                sum += count * partial_id;
+                count += 1;
+            }
+            empty_len -= len;
+            partial_len -= len;
+            if partial_len == 0 {
+                tail -= 2;
+            }
+        }
+        head += 1;
+    }
+    for _ in 0..partial_len {
+        sum += count * partial_id;
+        count += 1;
+    }
+    sum
+}
+
+enum Node {
+    Empty { len: u8 },
+    File { len: u8, id: usize },
+}
+
+/// Free spans indexed by length, each a min-heap of start positions, so the leftmost span
+/// that fits a file is the smallest top among the heaps for lengths of at least its size
+struct FreeSpans {
+    heaps: [BinaryHeap<Reverse<usize>>; 10],
+}
+
+impl FreeSpans {
+    fn push(&mut self, pos: usize, len: usize) {
+        if len > 0 {
+            self.heaps[len].push(Reverse(pos));
+        }
+    }
+
+    /// Take the leftmost span of at least `len` blocks that starts before `before`, returning
+    /// its position and putting back whatever the file doesn't use
+    fn take(&mut self, len: usize, before: usize) -> Option<usize> {
+        let (span_len, pos) = (len..self.heaps.len())
+            .filter_map(|l| self.heaps[l].peek().map(|&Reverse(pos)| (l, pos)))
+            .filter(|&(_, pos)| pos < before)
+            .min_by_key(|&(_, pos)| pos)?;
+        self.heaps[span_len].pop();
+        self.push(pos + len, span_len - len);
+        Some(pos)
+    }
+}
+
+fn part2(input: &str) -> usize {
+    let mut files = vec![];
+    let mut free = FreeSpans {
+        heaps: Default::default(),
+    };
+    let mut pos = 0;
+    for (i, &b) in input.trim_ascii_end().as_bytes().iter().enumerate() {
+        let len = (b - OFFSET) as usize;
+        if i % 2 == 0 {
+            files.push((pos, len));
+        } else {
+            free.push(pos, len);
+        }
+        pos += len;
+    }
+
+    // Files only move left, so the space a file leaves behind is never before a file
+    // that has yet to move
+    let mut sum = 0;
+    for (id, &(pos, len)) in files.iter().enumerate().rev() {
+        let pos = free.take(len, pos).unwrap_or(pos);
+        sum += id * (len * pos + len * len.saturating_sub(1) / 2);
+    }
+    sum
+}
+
+/// Moves files by scanning the disk from the start for every file, kept to check and
+/// benchmark `part2` against
+fn part2_naive(input: &str) -> usize {
+    let mut disk = input
+        .trim_ascii_end()
+        .as_bytes()
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            let len = b - OFFSET;
+            if i % 2 == 0 {
+                Node::File { len, id: i / 2 }
+            } else {
+                Node::Empty { len }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut i = disk.len() - 1;
+    while i > 0 {
+        if let Node::File { len: a, id } = disk[i] {
+            for j in 0..i {
+                match disk[j] {
+                    Node::Empty { len: b } if b >= a => {
+                        disk[i] = Node::Empty { len: a };
+                        disk[j] = Node::File { len: a, id };
+                        if b > a {
+                            disk.insert(j + 1, Node::Empty { len: b - a });
+                        }
+                        break;
+                    }
+                    _ => (),
+                }
+            }
+        }
+        i -= 1;
+    }
+
+    let mut sum = 0usize;
+    let mut count = 0usize;
+    for node in disk {
+        match node {
+            Node::Empty { len } => {
+                count += len as usize;
+            }
+            Node::File { len, id } => {
+                for _ in 0..len {
+                    sum += count * id;
+                    count += 1;
+                }
+            }
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Rng;
+
+    const EXAMPLE: &str = "2333133121414131402";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), 1928);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), 2858);
+        assert_eq!(part2_naive(EXAMPLE), 2858);
+    }
+
+    #[test]
+    fn layouts_like_puzzle() {
+        let mut disk = DiskMap::parse(EXAMPLE);
+        assert_eq!(disk.layout(), "00...111...2...333.44.5555.6666.777.888899");
+        let mut layouts = vec![];
+        disk.compact(Strategy::Files(Fit::First), |disk, _| {
+            layouts.push(disk.layout())
+        });
+        assert_eq!(
+            layouts,
+            [
+                "0099.111...2...333.44.5555.6666.777.8888..",
+                "0099.1117772...333.44.5555.6666.....8888..",
+                "0099.111777244.333....5555.6666.....8888..",
+                "00992111777.44.333....5555.6666.....8888..",
+            ]
+        );
+        assert_eq!(disk.checksum(), 2858);
+
+        let mut disk = DiskMap::parse(EXAMPLE);
+        let mut moves = 0;
+        disk.compact(Strategy::Blocks, |_, _| moves += 1);
+        assert_eq!(disk.layout(), "0099811188827773336446555566..............");
+        assert_eq!(moves, 12);
+        assert_eq!(disk.checksum(), 1928);
+
+        let wide = DiskMap::parse("1111111111111111111111");
+        assert!(wide.layout().starts_with("00 .. 01 .. 02"));
+        assert!(wide.layout().ends_with(".. 10 .."));
+    }
+
+    #[test]
+    fn strategies_keep_files() {
+        let input = "23331331214141314021";
+        for strategy in ["blocks", "first-fit", "best-fit", "min-fragments"] {
+            let mut disk = DiskMap::parse(input);
+            let before = disk.fragmentation();
+            disk.compact(strategy.parse().unwrap(), |_, _| ());
+            let after = disk.fragmentation();
+            assert_eq!(after.free_blocks, before.free_blocks, "{strategy}");
+            assert!(after.gap_blocks < before.gap_blocks, "{strategy}");
+            let mut ids = disk.blocks.iter().flatten().copied().collect::<Vec<_>>();
+            ids.sort();
+            let mut original = DiskMap::parse(input)
+                .blocks
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            original.sort();
+            assert_eq!(ids, original, "{strategy}");
+        }
+
+        // File 2 fills the second span exactly rather than splitting the first
+        let mut disk = DiskMap::parse("13122");
+        assert_eq!(disk.layout(), "0...1..22");
+        disk.compact(Strategy::Files(Fit::MinFragments), |_, _| ());
+        assert_eq!(disk.layout(), "01...22..");
+        let mut disk = DiskMap::parse("13122");
+        disk.compact(Strategy::Files(Fit::First), |_, _| ());
+        assert_eq!(disk.layout(), "0221.....");
+        let metrics = disk.fragmentation();
+        assert_eq!(metrics.file_fragments, 3);
+        assert_eq!(metrics.largest_free_span, 5);
+        assert_eq!(metrics.gap_blocks, 0);
+    }
+
+    #[test]
+    fn heaps_match_naive() {
+        let mut rng = Rng::new(9);
+        for _ in 0..200 {
+            let len = 1 + rng.below(200);
+            let input = (0..len)
+                .map(|i| {
+                    // Files are at least one block long, free spans may be empty
+                    let min = if i % 2 == 0 { 1 } else { 0 };
+                    char::from(b'0' + (min + rng.below(10 - min)) as u8)
+                })
+                .collect::<String>();
+            assert_eq!(part2(&input), part2_naive(&input), "{input}");
+        }
+    }
+}
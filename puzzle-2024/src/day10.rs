@@ -0,0 +1,458 @@
+use std::error::Error;
+
+use common::Runner;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 10)?;
+    let climb = climb_from_args(&runner)?;
+    let trailhead = runner.coord("--trailhead")?;
+    if runner.flag("--explore") || trailhead.is_some() {
+        let map = TrailMap::parse(runner.input());
+        let trails = Trails::new(&map, climb);
+        match trailhead {
+            Some(start) => {
+                let limit = trail_limit(runner.value("--trails"))?;
+                eprint!("{}", explore_trailhead(&map, &trails, start, limit)?)
+            }
+            None => eprint!("{}", explore(&map, &trails)),
+        }
+    }
+    if climb == Climb::PUZZLE {
+        runner.part(1, part1)?;
+        runner.part(2, part2)?;
+    } else {
+        let trails = |input: &str| Trails::new(&TrailMap::parse(input), climb);
+        runner.part(1, |input| trails(input).total_score())?;
+        runner.part(2, |input| trails(input).total_rating())?;
+    }
+    Ok(())
+}
+
+fn climb_from_args(runner: &Runner) -> Result<Climb, Box<dyn Error>> {
+    let mut climb = Climb::PUZZLE;
+    if let Some(h) = runner.value("--start") {
+        climb.start = parse_height(h)?;
+    }
+    if let Some(h) = runner.value("--end") {
+        climb.end = parse_height(h)?;
+    }
+    if let Some(n) = runner.value("--step") {
+        climb.step = n.parse()?;
+    }
+    if climb.step == 0
+        || climb.start > climb.end
+        || !(climb.end - climb.start).is_multiple_of(climb.step)
+    {
+        return Err(format!(
+            "can't climb from {} to {} in steps of {}",
+            climb.start, climb.end, climb.step
+        )
+        .into());
+    }
+    Ok(climb)
+}
+
+fn parse_height(s: &str) -> Result<u8, Box<dyn Error>> {
+    match s.as_bytes() {
+        &[b] => height(b).ok_or_else(|| format!("invalid height {s:?}").into()),
+        _ => Ok(s.parse()?),
+    }
+}
+
+/// How many trails `--trails` draws for a trailhead, where `all` draws every one
+fn trail_limit(value: Option<&str>) -> Result<usize, Box<dyn Error>> {
+    match value {
+        None => Ok(10),
+        Some("all") => Ok(usize::MAX),
+        Some(n) => Ok(n.parse()?),
+    }
+}
+
+const DELTAS: [(i32, i32); 4] = [(0, -1), (-1, 0), (0, 1), (1, 0)];
+
+/// Heights are `0-9` and then `a-z` for maps taller than the puzzle's, with anything else
+/// impassable
+fn height(b: u8) -> Option<u8> {
+    (b as char).to_digit(36).map(|h| h as u8)
+}
+
+struct TrailMap {
+    h: usize,
+    w: usize,
+    heights: Vec<Option<u8>>,
+}
+
+impl TrailMap {
+    fn parse(input: &str) -> Self {
+        let lines = input.lines().collect::<Vec<_>>();
+        let h = lines.len();
+        let w = lines.first().map_or(0, |line| line.len());
+        let mut heights = vec![None; h * w];
+        for (y, line) in lines.iter().enumerate() {
+            for (x, &b) in line.as_bytes().iter().enumerate().take(w) {
+                heights[y * w + x] = height(b);
+            }
+        }
+        Self { h, w, heights }
+    }
+
+    fn neighbors(&self, cell: usize) -> impl Iterator<Item = usize> + '_ {
+        let (y, x) = ((cell / self.w) as i32, (cell % self.w) as i32);
+        DELTAS.iter().filter_map(move |&(dy, dx)| {
+            let (y, x) = (y + dy, x + dx);
+            (y >= 0 && y < self.h as i32 && x >= 0 && x < self.w as i32)
+                .then(|| y as usize * self.w + x as usize)
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Climb {
+    start: u8,
+    end: u8,
+    step: u8,
+}
+
+impl Climb {
+    const PUZZLE: Self = Self {
+        start: 0,
+        end: 9,
+        step: 1,
+    };
+
+    /// The heights of a trail from the top down
+    fn levels(&self) -> impl DoubleEndedIterator<Item = u8> {
+        (self.start..=self.end).step_by(self.step as usize).rev()
+    }
+}
+
+/// For every cell on a trail, which summits it can reach and by how many distinct trails.
+/// Filled in one height at a time from the summits down, so every cell is visited once.
+struct Trails {
+    climb: Climb,
+    heights: Vec<Option<u8>>,
+    words: usize,
+    /// `reach[cell * words..][..words]` has a bit set for each reachable summit
+    reach: Vec<u64>,
+    paths: Vec<usize>,
+}
+
+impl Trails {
+    fn new(map: &TrailMap, climb: Climb) -> Self {
+        let cells = map.heights.len();
+        let at_height = |h: u8| (0..cells).filter(move |&cell| map.heights[cell] == Some(h));
+        let summits = at_height(climb.end).collect::<Vec<_>>();
+        let words = summits.len().div_ceil(64);
+        let mut reach = vec![0u64; cells * words];
+        let mut paths = vec![0; cells];
+        for (i, &summit) in summits.iter().enumerate() {
+            reach[summit * words + i / 64] |= 1 << (i % 64);
+            paths[summit] = 1;
+        }
+
+        for h in climb.levels().skip(1) {
+            let above = h + climb.step;
+            for cell in at_height(h) {
+                for next in map.neighbors(cell) {
+                    if map.heights[next] != Some(above) {
+                        continue;
+                    }
+                    paths[cell] += paths[next];
+                    for word in 0..words {
+                        reach[cell * words + word] |= reach[next * words + word];
+                    }
+                }
+            }
+        }
+        Self {
+            climb,
+            heights: map.heights.clone(),
+            words,
+            reach,
+            paths,
+        }
+    }
+
+    fn trailheads(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.heights.len()).filter(|&cell| self.heights[cell] == Some(self.climb.start))
+    }
+
+    fn score(&self, cell: usize) -> usize {
+        self.reach[cell * self.words..][..self.words]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    fn rating(&self, cell: usize) -> usize {
+        self.paths[cell]
+    }
+
+    fn total_score(&self) -> usize {
+        self.trailheads().map(|cell| self.score(cell)).sum()
+    }
+
+    fn total_rating(&self) -> usize {
+        self.trailheads().map(|cell| self.rating(cell)).sum()
+    }
+}
+
+/// How many trails starting at any of `starts` pass through each cell: the trails climbing
+/// from a start to the cell, times the trails from the cell on to a summit
+fn trails_through(map: &TrailMap, trails: &Trails, starts: &[usize]) -> Vec<usize> {
+    let mut up = vec![0; map.heights.len()];
+    for &start in starts {
+        up[start] = 1;
+    }
+    let climb = trails.climb;
+    for h in climb.levels().rev().skip(1) {
+        let below = h - climb.step;
+        for cell in (0..up.len()).filter(|&cell| map.heights[cell] == Some(h)) {
+            up[cell] = map
+                .neighbors(cell)
+                .filter(|&next| map.heights[next] == Some(below))
+                .map(|next| up[next])
+                .sum();
+        }
+    }
+    up.iter()
+        .zip(&trails.paths)
+        .map(|(up, down)| up * down)
+        .collect()
+}
+
+fn heat_map(map: &TrailMap, through: &[usize]) -> String {
+    const RAMP: &[u8] = b".:-=+*#%@";
+    let max = through.iter().copied().max().unwrap_or(0);
+    let mut out = String::new();
+    for (cell, &count) in through.iter().enumerate() {
+        let shade = match count {
+            0 => 0,
+            _ => 1 + (count * (RAMP.len() - 1) - 1) / max,
+        };
+        out.push(RAMP[shade] as char);
+        if cell % map.w == map.w - 1 {
+            out.push('\n');
+        }
+    }
+    out + &format!("{} trails through the busiest cell\n", max)
+}
+
+fn trails_from(map: &TrailMap, trails: &Trails, start: usize, limit: usize) -> Vec<Vec<usize>> {
+    let mut found = vec![];
+    let mut stack = vec![vec![start]];
+    while let Some(trail) = stack.pop() {
+        if found.len() == limit {
+            break;
+        }
+        let cell = trail[trail.len() - 1];
+        let Some(h) = map.heights[cell] else {
+            continue;
+        };
+        if h == trails.climb.end {
+            found.push(trail);
+            continue;
+        }
+        // Only follow cells that lead on to a summit, so every branch ends in a trail
+        let above = h + trails.climb.step;
+        let next = map
+            .neighbors(cell)
+            .filter(|&next| map.heights[next] == Some(above) && trails.paths[next] > 0)
+            .collect::<Vec<_>>();
+        for &next in next.iter().rev() {
+            let mut longer = trail.clone();
+            longer.push(next);
+            stack.push(longer);
+        }
+    }
+    found
+}
+
+fn explore(map: &TrailMap, trails: &Trails) -> String {
+    let mut out = String::new();
+    let starts = trails.trailheads().collect::<Vec<_>>();
+    for &cell in &starts {
+        out += &format!(
+            "{},{}: score {}, rating {}\n",
+            cell / map.w,
+            cell % map.w,
+            trails.score(cell),
+            trails.rating(cell)
+        );
+    }
+    out + &heat_map(map, &trails_through(map, trails, &starts))
+}
+
+fn explore_trailhead(
+    map: &TrailMap,
+    trails: &Trails,
+    (y, x): (usize, usize),
+    limit: usize,
+) -> Result<String, Box<dyn Error>> {
+    let start = y * map.w + x;
+    if y >= map.h || x >= map.w || map.heights[start] != Some(trails.climb.start) {
+        return Err(format!("{y},{x} is not a trailhead").into());
+    }
+    let through = trails_through(map, trails, &[start]);
+    let draw = |f: &dyn Fn(usize, char) -> char| {
+        let mut out = String::new();
+        for cell in 0..map.heights.len() {
+            let c = match map.heights[cell] {
+                Some(h) => char::from_digit(h as u32, 36).unwrap_or('?'),
+                None => '.',
+            };
+            out.push(f(cell, c));
+            if cell % map.w == map.w - 1 {
+                out.push('\n');
+            }
+        }
+        out
+    };
+    let summits = draw(&|cell, c| {
+        let reached = map.heights[cell] == Some(trails.climb.end) && through[cell] > 0;
+        if reached {
+            '*'
+        } else {
+            c
+        }
+    });
+    let paths = draw(&|cell, c| if through[cell] > 0 { c } else { '.' });
+    let rating = trails.rating(start);
+    let mut out = format!(
+        "{summits}\n{paths}\nscore {}, rating {rating}\n\n",
+        trails.score(start)
+    );
+    for (i, trail) in trails_from(map, trails, start, limit).iter().enumerate() {
+        let on_trail = draw(&|cell, c| if trail.contains(&cell) { c } else { '.' });
+        out += &format!("trail {} of {rating}\n{on_trail}\n", i + 1);
+    }
+    if rating > limit {
+        out += &format!("and {} more trails\n\n", rating - limit);
+    }
+    Ok(out + &heat_map(map, &through))
+}
+
+fn part1(input: &str) -> usize {
+    Trails::new(&TrailMap::parse(input), Climb::PUZZLE).total_score()
+}
+
+fn part2(input: &str) -> usize {
+    Trails::new(&TrailMap::parse(input), Climb::PUZZLE).total_rating()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+    const FORKED: &str = "...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), 36);
+        assert_eq!(part1(FORKED), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), 81);
+        let braided = ".....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9....";
+        assert_eq!(part2(braided), 3);
+    }
+
+    #[test]
+    fn explore_forked_trail() -> Result<(), Box<dyn Error>> {
+        let map = TrailMap::parse(FORKED);
+        let trails = Trails::new(&map, Climb::PUZZLE);
+        let explored = explore_trailhead(&map, &trails, (0, 3), 10)?;
+        let (summits, rest) = explored.split_once("\n\n").unwrap();
+        assert_eq!(
+            summits,
+            "...0...\n...1...\n...2...\n6543456\n7.....7\n8.....8\n*.....*"
+        );
+        assert!(rest.contains("score 2, rating 2\n"));
+        let left = "...0...\n...1...\n...2...\n6543...\n7......\n8......\n9......\n";
+        assert!(rest.contains(&format!("trail 1 of 2\n{left}")));
+        assert!(rest.contains("trail 2 of 2\n"));
+        assert!(!rest.contains("more trails"));
+        let heat = "...@...\n...@...\n...@...\n+++@+++\n+.....+\n+.....+\n+.....+\n";
+        assert!(rest.ends_with(&format!("{heat}2 trails through the busiest cell\n")));
+        assert!(explore_trailhead(&map, &trails, (1, 3), 10).is_err());
+
+        let map = TrailMap::parse(EXAMPLE);
+        let trails = Trails::new(&map, Climb::PUZZLE);
+        let drawn = explore_trailhead(&map, &trails, (0, 2), trail_limit(None)?)?;
+        assert!(drawn.contains("trail 10 of 20\n"));
+        assert!(!drawn.contains("trail 11 of 20\n"));
+        assert!(drawn.contains("and 10 more trails\n"));
+        let drawn = explore_trailhead(&map, &trails, (0, 2), trail_limit(Some("3"))?)?;
+        assert!(drawn.contains("trail 3 of 20\n"));
+        assert!(!drawn.contains("trail 4 of 20\n"));
+        assert!(drawn.contains("and 17 more trails\n"));
+        let drawn = explore_trailhead(&map, &trails, (0, 2), trail_limit(Some("all"))?)?;
+        assert!(drawn.contains("trail 20 of 20\n"));
+        assert!(!drawn.contains("more trails"));
+        assert!(trail_limit(Some("some")).is_err());
+        let all = trails_from(&map, &trails, 2, usize::MAX);
+        assert_eq!(all.len(), 20);
+        assert!(all.iter().all(|trail| trail.len() == 10));
+
+        let explored = explore(&map, &trails);
+        assert!(explored.starts_with("0,2: score 5, rating 20\n0,4: score 6, rating 24\n"));
+        let through = trails_through(&map, &trails, &trails.trailheads().collect::<Vec<_>>());
+        let summits = (0..through.len()).filter(|&c| map.heights[c] == Some(9));
+        assert_eq!(summits.map(|c| through[c]).sum::<usize>(), 81);
+        Ok(())
+    }
+
+    #[test]
+    fn custom_climb() {
+        let map = TrailMap::parse("02468\n1357a\n.....");
+        let evens = Climb {
+            start: 0,
+            end: 8,
+            step: 2,
+        };
+        let trails = Trails::new(&map, evens);
+        assert_eq!((trails.total_score(), trails.total_rating()), (1, 1));
+
+        let tall = Climb {
+            start: 1,
+            end: 10,
+            step: 1,
+        };
+        let trails = Trails::new(&TrailMap::parse("9a\n8.\n7.\n6.\n5.\n4.\n3.\n2.\n1."), tall);
+        assert_eq!((trails.total_score(), trails.total_rating()), (1, 1));
+
+        // More summits than fit in one word of the bitset
+        let wide = format!("{}\n{}", "9".repeat(100), "8".repeat(100));
+        let one_step = Climb {
+            start: 8,
+            end: 9,
+            step: 1,
+        };
+        let trails = Trails::new(&TrailMap::parse(&wide), one_step);
+        assert_eq!(trails.score(100 + 70), 1);
+        assert_eq!(trails.total_score(), 100);
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    error::Error,
+    str::FromStr,
+};
+
+use common::{OrOverflow, Overflow, Runner};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 11)?;
+    let rules = match runner.value("--rules") {
+        Some(rules) => rules.parse()?,
+        None => RuleSet::puzzle(),
+    };
+    if let Some(blinks) = runner.value("--blinks") {
+        let blinks = blinks.parse()?;
+        let mut blinker = Blinker::new(rules.clone());
+        match runner.value("--stone") {
+            Some(stone) => {
+                let count = blinker.count(stone.parse()?, blinks)?;
+                eprintln!("stone {stone} after {blinks} blinks: {count} stones");
+            }
+            None => {
+                let count = blinker.count_all(&parse(runner.input())?, blinks)?;
+                eprintln!("after {blinks} blinks: {count} stones");
+            }
+        }
+    }
+    let count = |stones: Vec<u64>, blinks| Blinker::new(rules.clone()).count_all(&stones, blinks);
+    runner.part_with(1, parse, |stones| count(stones, 25))?;
+    runner.part_with(2, parse, |stones| count(stones, 75))?;
+    Ok(())
+}
+
+fn parse(input: &str) -> Result<Vec<u64>, Box<dyn Error>> {
+    Ok(input
+        .split_ascii_whitespace()
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()?)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Rule {
+    Replace { from: u64, to: u64 },
+    SplitEvenDigits,
+    Multiply { factor: u64 },
+}
+
+impl Rule {
+    /// The stones replacing `stone`, or `None` if the rule doesn't apply to it
+    fn apply(&self, stone: u64) -> Option<Result<Vec<u64>, Overflow>> {
+        match *self {
+            Rule::Replace { from, to } => (stone == from).then(|| Ok(vec![to])),
+            Rule::SplitEvenDigits => {
+                let digits = stone.checked_ilog10()? + 1;
+                digits.is_multiple_of(2).then(|| {
+                    let div = 10u64.pow(digits / 2);
+                    Ok(vec![stone / div, stone % div])
+                })
+            }
+            Rule::Multiply { factor } => Some(
+                stone
+                    .checked_mul(factor)
+                    .or_overflow("engraving a stone")
+                    .map(|stone| vec![stone]),
+            ),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid rule {s:?}, expected N->M, split or *N");
+        if s == "split" {
+            Ok(Rule::SplitEvenDigits)
+        } else if let Some(factor) = s.strip_prefix('*') {
+            let factor = factor.parse().map_err(|_| invalid())?;
+            Ok(Rule::Multiply { factor })
+        } else {
+            let (from, to) = s.split_once("->").ok_or_else(invalid)?;
+            let from = from.trim().parse().map_err(|_| invalid())?;
+            let to = to.trim().parse().map_err(|_| invalid())?;
+            Ok(Rule::Replace { from, to })
+        }
+    }
+}
+
+/// Rules tried in order, where the first one that applies decides what a stone becomes. A
+/// stone no rule applies to stays as it is.
+#[derive(Clone, Debug, PartialEq)]
+struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    fn puzzle() -> Self {
+        Self {
+            rules: vec![
+                Rule::Replace { from: 0, to: 1 },
+                Rule::SplitEvenDigits,
+                Rule::Multiply { factor: 2024 },
+            ],
+        }
+    }
+
+    fn blink(&self, stone: u64) -> Result<Vec<u64>, Overflow> {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.apply(stone))
+            .unwrap_or(Ok(vec![stone]))
+    }
+}
+
+impl FromStr for RuleSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rules = s
+            .split(',')
+            .map(|rule| rule.trim().parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+}
+
+/// Remembers what each stone becomes and how many stones it turns into after each number of
+/// blinks, for later queries to reuse
+struct Blinker {
+    rules: RuleSet,
+    expansions: HashMap<u64, Vec<u64>>,
+    memo: HashMap<(u64, u32), u128>,
+}
+
+impl Blinker {
+    fn new(rules: RuleSet) -> Self {
+        Self {
+            rules,
+            expansions: HashMap::new(),
+            memo: HashMap::new(),
+        }
+    }
+
+    fn count(&mut self, stone: u64, blinks: u32) -> Result<u128, Overflow> {
+        if blinks == 0 {
+            return Ok(1);
+        }
+        if let Some(&count) = self.memo.get(&(stone, blinks)) {
+            return Ok(count);
+        }
+        // Blink forwards to find every stone whose count isn't known for the blinks it has
+        // left, then fill in their counts from the last blink back to the first. Neither pass
+        // recurses, so the number of blinks doesn't matter to the stack.
+        let mut levels = vec![HashSet::from([stone])];
+        for left in (2..=blinks).rev() {
+            let mut next = HashSet::new();
+            for &stone in &levels[levels.len() - 1] {
+                let after = match self.expansions.entry(stone) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(self.rules.blink(stone)?),
+                };
+                next.extend(
+                    after
+                        .iter()
+                        .filter(|&&after| !self.memo.contains_key(&(after, left - 1))),
+                );
+            }
+            if next.is_empty() {
+                break;
+            }
+            levels.push(next);
+        }
+        for (k, level) in levels.iter().enumerate().rev() {
+            let left = blinks - k as u32;
+            for &stone in level {
+                if left == 1 && !self.expansions.contains_key(&stone) {
+                    self.expansions.insert(stone, self.rules.blink(stone)?);
+                }
+                let mut count = 0u128;
+                for &after in &self.expansions[&stone] {
+                    let n = if left == 1 {
+                        1
+                    } else {
+                        self.memo[&(after, left - 1)]
+                    };
+                    count = count.checked_add(n).or_overflow("counting stones")?;
+                }
+                self.memo.insert((stone, left), count);
+            }
+        }
+        Ok(self.memo[&(stone, blinks)])
+    }
+
+    fn count_all(&mut self, stones: &[u64], blinks: u32) -> Result<u128, Overflow> {
+        stones.iter().try_fold(0u128, |sum, &stone| {
+            sum.checked_add(self.count(stone, blinks)?)
+                .or_overflow("counting stones")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "125 17";
+
+    #[test]
+    fn part1_example() -> Result<(), Box<dyn Error>> {
+        let mut blinker = Blinker::new(RuleSet::puzzle());
+        assert_eq!(blinker.count_all(&parse(EXAMPLE)?, 25)?, 55312);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<(), Box<dyn Error>> {
+        let mut blinker = Blinker::new(RuleSet::puzzle());
+        assert_eq!(blinker.count_all(&parse(EXAMPLE)?, 75)?, 65601038650482);
+        Ok(())
+    }
+
+    #[test]
+    fn queries_share_counts() -> Result<(), Box<dyn Error>> {
+        let mut blinker = Blinker::new(RuleSet::puzzle());
+        assert_eq!(
+            RuleSet::puzzle().blink(1000)?,
+            [10, 0],
+            "splitting drops leading zeros"
+        );
+        assert_eq!(blinker.count_all(&[125, 17], 6)?, 22);
+        // 125 becomes 253000 on the first blink, so that count is already known
+        let remembered = blinker.memo.len();
+        assert_eq!(blinker.count(253000, 5)?, blinker.memo[&(253000, 5)]);
+        assert_eq!(blinker.count(125, 6)?, blinker.memo[&(125, 6)]);
+        assert_eq!(blinker.memo.len(), remembered);
+        // 0 becomes 1, whose count after 30 blinks is then already known
+        let known = blinker.count(1, 30)?;
+        assert_eq!(blinker.count(0, 31)?, known);
+        assert_eq!(Blinker::new(RuleSet::puzzle()).count(0, 31)?, known);
+        // Far past the point where a usize total would overflow
+        assert!(blinker.count(0, 150)? > u64::MAX as u128);
+
+        assert_eq!("0->1,split,*2024".parse::<RuleSet>()?, RuleSet::puzzle());
+        assert!("0->1,double".parse::<RuleSet>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn stones_near_limit() -> Result<(), Box<dyn Error>> {
+        // 19 digits, so the stone is multiplied by 2024 instead of split
+        let mut blinker = Blinker::new(RuleSet::puzzle());
+        let err = blinker.count(1_000_000_000_000_000_000, 25).unwrap_err();
+        assert_eq!(err.to_string(), "integer overflow while engraving a stone");
+        // 20 digits splits cleanly however large it is
+        assert_eq!(blinker.count(u64::MAX, 1)?, 2);
+
+        // 1 -> 11 -> 1 1 doubles the stones every other blink, until the total needs 129 bits
+        let mut blinker = Blinker::new("1->11,split".parse()?);
+        assert_eq!(blinker.count(1, 254)?, 1 << 127);
+        let err = blinker.count(1, 256).unwrap_err();
+        assert_eq!(err.context, "counting stones");
+
+        // Rule sets that never grow can blink far more often than the stack is deep
+        let mut blinker = Blinker::new("0->1,1->0".parse()?);
+        assert_eq!(blinker.count(0, 200_000)?, 1);
+        Ok(())
+    }
+}
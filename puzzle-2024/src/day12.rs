@@ -1,11 +1,11 @@
-use std::{collections::HashSet, error::Error, fs::read_to_string, iter::zip, time::Instant};
+use std::{collections::HashSet, error::Error, iter::zip};
+
+use common::Runner;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let input = read_to_string("input/day12.txt")?;
-    let now = Instant::now();
-    println!("p1: {} ({}µs)", part1(&input), now.elapsed().as_micros());
-    let now = Instant::now();
-    println!("p2: {} ({}µs)", part2(&input), now.elapsed().as_micros());
+    let runner = Runner::new(2024, 12)?;
+    runner.part(1, part1)?;
+    runner.part(2, part2)?;
     Ok(())
 }
 
@@ -105,7 +105,7 @@ fn part2(input: &str) -> usize {
             }
             sides += 1;
             let (mut y0, mut x0) = perimeter[0];
-            for &(y1, x1) in perimeter[1..].into_iter() {
+            for &(y1, x1) in perimeter[1..].iter() {
                 if y1.abs_diff(y0) + x1.abs_diff(x0) != 1 {
                     sides += 1;
                 }
@@ -0,0 +1,194 @@
+use std::{error::Error, str::FromStr};
+
+use common::{OrOverflow, Overflow, Runner};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let runner = Runner::new(2024, 13)?;
+    runner.part_with(1, parse, |machines| total_cost(&machines))?;
+    runner.part_with(2, parse, |machines| corrected_cost(&machines))?;
+    Ok(())
+}
+
+fn corrected_cost(machines: &[Machine]) -> Result<i64, Overflow> {
+    let machines = machines
+        .iter()
+        .map(Machine::corrected)
+        .collect::<Result<Vec<_>, _>>()?;
+    total_cost(&machines)
+}
+
+fn total_cost(machines: &[Machine]) -> Result<i64, Overflow> {
+    let mut total = 0i64;
+    for machine in machines {
+        if let Some(cost) = machine.cost()? {
+            total = total.checked_add(cost).or_overflow("summing costs")?;
+        }
+    }
+    Ok(total)
+}
+
+fn parse(input: &str) -> Result<Vec<Machine>, Box<dyn Error>> {
+    let machines = input
+        .split("\n\n")
+        .map(Machine::from_str)
+        .collect::<Result<_, _>>()?;
+    Ok(machines)
+}
+
+impl FromStr for Machine {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let xys = s
+            .lines()
+            .map(|line| {
+                line.split_once(": ")
+                    .ok_or("missing colon delimiter")
+                    .and_then(|(_, rest)| rest.split_once(", ").ok_or("missing comma delimiter"))
+                    .and_then(|(x, y)| {
+                        x[2..]
+                            .parse::<i64>()
+                            .map_err(|_| "invalid x integer")
+                            .and_then(|x| {
+                                y[2..]
+                                    .parse::<i64>()
+                                    .map_err(|_| "invalid y integer")
+                                    .map(|y| (x, y))
+                            })
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Machine::new(
+            xys[0].0, xys[0].1, xys[1].0, xys[1].1, xys[2].0, xys[2].1,
+        ))
+    }
+}
+
+struct Machine {
+    ax: i64,
+    ay: i64,
+    bx: i64,
+    by: i64,
+    px: i64,
+    py: i64,
+}
+
+impl Machine {
+    fn new(ax: i64, ay: i64, bx: i64, by: i64, px: i64, py: i64) -> Self {
+        Self {
+            ax,
+            ay,
+            bx,
+            by,
+            px,
+            py,
+        }
+    }
+
+    fn corrected(&self) -> Result<Self, Overflow> {
+        let offset = 10000000000000;
+        Ok(Self {
+            px: self
+                .px
+                .checked_add(offset)
+                .or_overflow("correcting the prize")?,
+            py: self
+                .py
+                .checked_add(offset)
+                .or_overflow("correcting the prize")?,
+            ..*self
+        })
+    }
+
+    /// The tokens needed to win the prize, if it can be won. Solves for the presses with
+    /// Cramer's rule in `i128`, where the products of two `i64` coordinates can't overflow,
+    /// although their differences still can at the very extremes.
+    fn cost(&self) -> Result<Option<i64>, Overflow> {
+        let [ax, ay, bx, by, px, py] =
+            [self.ax, self.ay, self.bx, self.by, self.px, self.py].map(i128::from);
+        let context = "solving for button presses";
+        let nb_num = (ax * py).checked_sub(px * ay).or_overflow(context)?;
+        let nb_den = (ax * by).checked_sub(ay * bx).or_overflow(context)?;
+        // Parallel buttons don't have a unique solution, and don't occur in the puzzle input
+        if nb_den == 0 || nb_num % nb_den != 0 {
+            return Ok(None);
+        }
+        let nb = nb_num / nb_den;
+        // Button A moves along at least one axis, or the buttons would be parallel
+        let (a, b, p) = if ax != 0 { (ax, bx, px) } else { (ay, by, py) };
+        let na_num = nb
+            .checked_mul(b)
+            .and_then(|n| p.checked_sub(n))
+            .or_overflow(context)?;
+        if a == 0 || na_num % a != 0 {
+            return Ok(None);
+        }
+        let na = na_num / a;
+        if na < 0 || nb < 0 {
+            return Ok(None);
+        }
+        let cost = na.checked_mul(3).and_then(|n| n.checked_add(nb));
+        cost.and_then(|cost| i64::try_from(cost).ok())
+            .or_overflow("counting tokens")
+            .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    fn part1_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(total_cost(&parse(EXAMPLE)?)?, 480);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<(), Box<dyn Error>> {
+        assert_eq!(corrected_cost(&parse(EXAMPLE)?)?, 875318608908);
+        Ok(())
+    }
+
+    #[test]
+    fn costs_near_limit() {
+        // 4e17 presses of each button, where a * p overflows i64 but not the token count
+        let n = 400_000_000_000_000_000;
+        let machine = Machine::new(10, 1, 1, 10, 11 * n, 11 * n);
+        assert_eq!(machine.cost(), Ok(Some(4 * n)));
+
+        // 4e18 presses of A cost more tokens than fit in an i64
+        let machine = Machine::new(1, 1, 1, 2, 10 * n + 1, 10 * n + 2);
+        assert_eq!(
+            machine.cost().unwrap_err().to_string(),
+            "integer overflow while counting tokens"
+        );
+
+        let machine = Machine::new(94, 34, 22, 67, i64::MAX - 5, 5400);
+        assert!(machine.corrected().is_err());
+
+        // Reaching the prize would take a negative number of presses
+        assert_eq!(Machine::new(1, 0, 0, 1, -3, 4).cost(), Ok(None));
+        assert_eq!(Machine::new(1, 2, 2, 4, 3, 6).cost(), Ok(None));
+
+        // Button A only moves along Y, so its presses come from the Y equation
+        assert_eq!(Machine::new(0, 1, 1, 0, 5, 3).cost(), Ok(Some(14)));
+        assert_eq!(Machine::new(0, 2, 1, 0, 5, 3).cost(), Ok(None));
+    }
+}
@@ -1,11 +1,11 @@
-use std::{collections::HashSet, error::Error, fs::read_to_string, str::FromStr, time::Instant};
+use std::{collections::HashSet, error::Error, str::FromStr};
+
+use common::Runner;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let input = read_to_string("input/day14.txt")?;
-    let now = Instant::now();
-    println!("p1: {} ({}µs)", part1(&input)?, now.elapsed().as_micros());
-    let now = Instant::now();
-    println!("p2: {} ({}µs)", part2(&input)?, now.elapsed().as_micros());
+    let runner = Runner::new(2024, 14)?;
+    runner.part(1, part1)?;
+    runner.part(2, part2)?;
     Ok(())
 }
 
@@ -62,10 +62,8 @@ fn calc_regularity(robots: &[Robot], w: i32, h: i32, i: i32) -> usize {
     }
     let visited: Vec<_> = visited.into_iter().collect();
     let mut regular = 0;
-    for i in 0..visited.len() {
-        let (x0, y0) = visited[i];
-        for j in i..visited.len() {
-            let (x1, y1) = visited[j];
+    for (i, &(x0, y0)) in visited.iter().enumerate() {
+        for &(x1, y1) in &visited[i..] {
             let (dx, dy) = (x1.abs_diff(x0), y1.abs_diff(y0));
             if dx == 1 && dy == 0 || dx == 0 && dy == 1 || dx == 1 && dy == 1 {
                 regular += 1;